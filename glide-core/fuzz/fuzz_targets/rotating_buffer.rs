@@ -0,0 +1,19 @@
+/*
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+#![no_main]
+
+use glide_core::{redis_request::RedisRequest, rotating_buffer::RotatingBuffer};
+use libfuzzer_sys::fuzz_target;
+
+// The socket listener reads the requests of the bindings in chunks, so the input is split into chunks that are
+// written to the buffer one after the other. Malformed requests must fail the parsing instead of panicking.
+fuzz_target!(|chunks: Vec<Vec<u8>>| {
+    let mut rotating_buffer = RotatingBuffer::new(1024);
+    for chunk in chunks {
+        rotating_buffer.current_buffer().extend_from_slice(&chunk);
+        if rotating_buffer.get_requests::<RedisRequest>().is_err() {
+            break;
+        }
+    }
+});
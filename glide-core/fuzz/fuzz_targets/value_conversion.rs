@@ -0,0 +1,56 @@
+/*
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+#![no_main]
+
+use glide_core::client::convert_response;
+use libfuzzer_sys::fuzz_target;
+
+/// Commands whose responses are converted, along with the arguments that affect the conversion.
+const COMMANDS: &[&[&str]] = &[
+    &["HGETALL"],
+    &["XCLAIM"],
+    &["XCLAIM", "JUSTID"],
+    &["XAUTOCLAIM"],
+    &["XRANGE"],
+    &["XREAD"],
+    &["LCS", "IDX"],
+    &["INCRBYFLOAT"],
+    &["HEXISTS"],
+    &["SMISMEMBER"],
+    &["SMEMBERS"],
+    &["ZSCORE"],
+    &["ZMSCORE"],
+    &["ZPOPMIN"],
+    &["BZPOPMIN"],
+    &["BZMPOP"],
+    &["JSON.TOGGLE"],
+    &["GEOPOS"],
+    &["GEOSEARCH", "WITHDIST", "WITHHASH", "WITHCOORD"],
+    &["LMPOP"],
+    &["HRANDFIELD", "WITHVALUES"],
+    &["ZRANDMEMBER", "WITHSCORES"],
+    &["ZADD", "INCR"],
+    &["ZRANGE", "WITHSCORES"],
+    &["ZRANK", "WITHSCORE"],
+    &["SPOP"],
+    &["LOLWUT"],
+    &["FUNCTION", "LIST"],
+    &["FUNCTION", "STATS"],
+];
+
+// The first byte of the input selects the command, and the rest is parsed as the server's RESP response. Unexpected
+// responses must fail the conversion instead of panicking.
+fuzz_target!(|data: &[u8]| {
+    let Some((selector, response)) = data.split_first() else {
+        return;
+    };
+    let Ok(value) = redis::parse_redis_value(response) else {
+        return;
+    };
+    let mut cmd = redis::Cmd::new();
+    for arg in COMMANDS[*selector as usize % COMMANDS.len()] {
+        cmd.arg(*arg);
+    }
+    let _ = convert_response(&cmd, value);
+});
@@ -0,0 +1,252 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::{Client, ClientWrapper};
+use redis::cluster_routing::{RoutingInfo, SingleNodeRoutingInfo};
+use redis::{Cmd, ErrorKind, RedisError, RedisResult, Value};
+
+/// The commands of a batch that are sent to a single node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubBatch {
+    /// The address of the node, formatted as `host:port`.
+    pub address: String,
+    /// The indices of the sub-batch's commands in the original batch, in the order they should be sent.
+    pub command_indices: Vec<usize>,
+}
+
+/// A batch split into sub-batches by the nodes owning the commands' slots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchPlan {
+    pub sub_batches: Vec<SubBatch>,
+    command_count: usize,
+}
+
+impl BatchPlan {
+    /// Merges the responses of the sub-batches, given in the order of `sub_batches`, into the order of the original
+    /// batch.
+    pub fn merge(&self, sub_batch_responses: Vec<Vec<Value>>) -> RedisResult<Vec<Value>> {
+        if sub_batch_responses.len() != self.sub_batches.len() {
+            return Err(RedisError::from((
+                ErrorKind::ClientError,
+                "Expected a response for every sub-batch",
+            )));
+        }
+        let mut responses = vec![Value::Nil; self.command_count];
+        for (sub_batch, sub_batch_responses) in self.sub_batches.iter().zip(sub_batch_responses) {
+            if sub_batch_responses.len() != sub_batch.command_indices.len() {
+                return Err(RedisError::from((
+                    ErrorKind::ClientError,
+                    "Expected a response for every command of the sub-batch",
+                    sub_batch.address.clone(),
+                )));
+            }
+            for (index, response) in sub_batch.command_indices.iter().zip(sub_batch_responses) {
+                responses[*index] = response;
+            }
+        }
+        Ok(responses)
+    }
+}
+
+/// Parses the output of `CLUSTER SLOTS` into slot ranges and the addresses of their primaries.
+fn parse_cluster_slots(cluster_slots: Value) -> RedisResult<Vec<(u16, u16, String)>> {
+    let invalid_response =
+        || RedisError::from((ErrorKind::TypeError, "Unexpected CLUSTER SLOTS response"));
+    let Value::Array(ranges) = cluster_slots else {
+        return Err(invalid_response());
+    };
+    ranges
+        .into_iter()
+        .map(|range| {
+            let Value::Array(range) = range else {
+                return Err(invalid_response());
+            };
+            let (Some(Value::Int(start)), Some(Value::Int(end)), Some(Value::Array(primary))) =
+                (range.first(), range.get(1), range.get(2))
+            else {
+                return Err(invalid_response());
+            };
+            let host = match primary.first() {
+                Some(Value::BulkString(host)) => String::from_utf8_lossy(host).into_owned(),
+                Some(Value::SimpleString(host)) => host.clone(),
+                _ => return Err(invalid_response()),
+            };
+            let Some(Value::Int(port)) = primary.get(1) else {
+                return Err(invalid_response());
+            };
+            Ok((*start as u16, *end as u16, format!("{host}:{port}")))
+        })
+        .collect()
+}
+
+/// Groups the commands by the primaries owning their slots. Commands without a key are added to the first sub-batch.
+fn split_batch_by_slots(
+    cmds: &[Cmd],
+    slot_ranges: &[(u16, u16, String)],
+) -> RedisResult<BatchPlan> {
+    let mut sub_batches: Vec<SubBatch> = Vec::new();
+    let mut keyless_indices = Vec::new();
+    for (index, cmd) in cmds.iter().enumerate() {
+        let slot = match RoutingInfo::for_routable(cmd) {
+            Some(RoutingInfo::SingleNode(SingleNodeRoutingInfo::SpecificNode(route))) => {
+                route.slot()
+            }
+            Some(RoutingInfo::MultiNode(_)) => {
+                return Err(RedisError::from((
+                    ErrorKind::ClientError,
+                    "Commands sent to multiple nodes can't be split into sub-batches",
+                    format!("command at index {index}"),
+                )))
+            }
+            _ => {
+                keyless_indices.push(index);
+                continue;
+            }
+        };
+        let Some((_, _, address)) = slot_ranges
+            .iter()
+            .find(|(start, end, _)| (*start..=*end).contains(&slot))
+        else {
+            return Err(RedisError::from((
+                ErrorKind::ClusterDown,
+                "Slot isn't covered by any node",
+                format!("slot {slot}"),
+            )));
+        };
+        match sub_batches
+            .iter_mut()
+            .find(|sub_batch| sub_batch.address == *address)
+        {
+            Some(sub_batch) => sub_batch.command_indices.push(index),
+            None => sub_batches.push(SubBatch {
+                address: address.clone(),
+                command_indices: vec![index],
+            }),
+        }
+    }
+    if !keyless_indices.is_empty() {
+        match sub_batches.first_mut() {
+            Some(sub_batch) => {
+                sub_batch.command_indices.extend(keyless_indices);
+                sub_batch.command_indices.sort_unstable();
+            }
+            None => {
+                let Some((_, _, address)) = slot_ranges.first() else {
+                    return Err(RedisError::from((
+                        ErrorKind::ClusterDown,
+                        "No slots are covered",
+                    )));
+                };
+                sub_batches.push(SubBatch {
+                    address: address.clone(),
+                    command_indices: keyless_indices,
+                });
+            }
+        }
+    }
+    Ok(BatchPlan {
+        sub_batches,
+        command_count: cmds.len(),
+    })
+}
+
+impl Client {
+    /// Splits a batch of commands into sub-batches, one per primary owning the commands' slots, according to the
+    /// current topology. The sub-batches keep the original order of their commands, and their responses can be merged
+    /// back into the original order with `BatchPlan::merge`.
+    pub async fn split_batch(&mut self, cmds: &[Cmd]) -> RedisResult<BatchPlan> {
+        if matches!(self.internal_client, ClientWrapper::Standalone(_)) {
+            return Err(RedisError::from((
+                ErrorKind::ClientError,
+                "Batch splitting is supported only in cluster mode",
+            )));
+        }
+        let mut cluster_slots_cmd = redis::cmd("CLUSTER");
+        cluster_slots_cmd.arg("SLOTS");
+        let cluster_slots = self
+            .send_command(
+                &cluster_slots_cmd,
+                Some(RoutingInfo::SingleNode(SingleNodeRoutingInfo::Random)),
+            )
+            .await?;
+        split_batch_by_slots(cmds, &parse_cluster_slots(cluster_slots)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_cluster_slots, split_batch_by_slots, SubBatch};
+    use redis::{cmd, Cmd, Value};
+
+    fn slot_ranges() -> Vec<(u16, u16, String)> {
+        vec![
+            (0, 8191, "node1:6379".to_string()),
+            (8192, 16383, "node2:6379".to_string()),
+        ]
+    }
+
+    fn get(key: &str) -> Cmd {
+        let mut get = cmd("GET");
+        get.arg(key);
+        get
+    }
+
+    #[test]
+    fn test_parse_cluster_slots() {
+        let node = |host: &str, port| {
+            Value::Array(vec![
+                Value::BulkString(host.as_bytes().to_vec()),
+                Value::Int(port),
+                Value::BulkString(b"id".to_vec()),
+            ])
+        };
+        let cluster_slots = Value::Array(vec![Value::Array(vec![
+            Value::Int(0),
+            Value::Int(16383),
+            node("primary", 6379),
+            node("replica", 6380),
+        ])]);
+        assert_eq!(
+            parse_cluster_slots(cluster_slots).unwrap(),
+            vec![(0, 16383, "primary:6379".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_split_batch_groups_by_node_and_merges_in_order() {
+        // "a" hashes to slot 15495, "b" to 3300, and "{a}b" to the slot of "a".
+        let cmds = vec![get("a"), get("b"), cmd("TIME"), get("{a}b")];
+        let plan = split_batch_by_slots(&cmds, &slot_ranges()).unwrap();
+        assert_eq!(
+            plan.sub_batches,
+            vec![
+                SubBatch {
+                    address: "node2:6379".to_string(),
+                    command_indices: vec![0, 2, 3],
+                },
+                SubBatch {
+                    address: "node1:6379".to_string(),
+                    command_indices: vec![1],
+                },
+            ]
+        );
+
+        let responses = plan
+            .merge(vec![
+                vec![Value::Int(0), Value::Int(2), Value::Int(3)],
+                vec![Value::Int(1)],
+            ])
+            .unwrap();
+        assert_eq!(
+            responses,
+            vec![Value::Int(0), Value::Int(1), Value::Int(2), Value::Int(3)]
+        );
+    }
+
+    #[test]
+    fn test_merge_requires_all_responses() {
+        let plan = split_batch_by_slots(&[get("a")], &slot_ranges()).unwrap();
+        assert!(plan.merge(vec![vec![]]).is_err());
+        assert!(plan.merge(vec![]).is_err());
+    }
+}
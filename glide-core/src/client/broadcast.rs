@@ -0,0 +1,174 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::{Client, ClientWrapper};
+use futures::future::{join_all, try_join_all};
+use redis::cluster_routing::{RoutingInfo, SingleNodeRoutingInfo};
+use redis::{Cmd, ErrorKind, RedisError, RedisResult, Value};
+
+/// How a broadcast handles nodes that return an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastFailurePolicy {
+    /// Return the first error.
+    FailFast,
+    /// Report each node's response or error.
+    CollectAll,
+}
+
+/// Parses the output of `CLUSTER NODES` into the addresses of the reachable nodes.
+fn parse_cluster_nodes(cluster_nodes: &str, primaries_only: bool) -> Vec<(String, u16)> {
+    cluster_nodes
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let address = parts.nth(1)?;
+            let flags: Vec<&str> = parts.next()?.split(',').collect();
+            if flags
+                .iter()
+                .any(|flag| matches!(*flag, "fail" | "noaddr" | "handshake"))
+                || (primaries_only && !flags.contains(&"master"))
+            {
+                return None;
+            }
+            // The address is formatted as `ip:port@cport[,hostname[,aux-field=value]*]`. Nodes that announce a hostname
+            // are addressed by it, since their IP may not be reachable from the client.
+            let (address, bus_address) = address.split_once('@').unwrap_or((address, ""));
+            let (ip, port) = address.rsplit_once(':')?;
+            let hostname = bus_address
+                .split(',')
+                .nth(1)
+                .filter(|hostname| !hostname.is_empty() && !hostname.contains('='));
+            let host = hostname.unwrap_or(ip);
+            if host.is_empty() {
+                return None;
+            }
+            Some((host.to_string(), port.parse().ok()?))
+        })
+        .collect()
+}
+
+fn node_response(result: RedisResult<Value>) -> Value {
+    match result {
+        Ok(value) => Value::Map(vec![(Value::SimpleString("value".to_string()), value)]),
+        Err(err) => Value::Map(vec![(
+            Value::SimpleString("error".to_string()),
+            Value::SimpleString(err.to_string()),
+        )]),
+    }
+}
+
+pub(super) fn parse_cluster_nodes_response(cluster_nodes: Value) -> RedisResult<String> {
+    match cluster_nodes {
+        Value::BulkString(bytes) => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+        Value::VerbatimString { text, .. } | Value::SimpleString(text) => Ok(text),
+        other => Err(RedisError::from((
+            ErrorKind::TypeError,
+            "Unexpected CLUSTER NODES response",
+            format!("{other:?}"),
+        ))),
+    }
+}
+
+impl Client {
+    /// Returns the output of `CLUSTER NODES`, from a random node.
+    async fn cluster_nodes(&mut self) -> RedisResult<String> {
+        let mut cluster_nodes_cmd = redis::cmd("CLUSTER");
+        cluster_nodes_cmd.arg("NODES");
+        let cluster_nodes = self
+            .send_command(
+                &cluster_nodes_cmd,
+                Some(RoutingInfo::SingleNode(SingleNodeRoutingInfo::Random)),
+            )
+            .await?;
+        parse_cluster_nodes_response(cluster_nodes)
+    }
+
+    /// Returns the addresses of the cluster's reachable nodes, or only of its primaries.
+    pub(super) async fn cluster_node_addresses(
+        &mut self,
+        primaries_only: bool,
+    ) -> RedisResult<Vec<(String, u16)>> {
+        let cluster_nodes = self.cluster_nodes().await?;
+        Ok(parse_cluster_nodes(&cluster_nodes, primaries_only))
+    }
+
+    /// Sends the command to every node in the cluster, or to every primary, and returns a map from each node's
+    /// address to its response. With `CollectAll`, each node's entry is a map containing either its `value` or its
+    /// `error`, so that a partial failure doesn't hide the responses of the other nodes.
+    pub async fn broadcast(
+        &mut self,
+        cmd: &Cmd,
+        primaries_only: bool,
+        failure_policy: BroadcastFailurePolicy,
+    ) -> RedisResult<Value> {
+        if matches!(self.internal_client, ClientWrapper::Standalone(_)) {
+            return Err(RedisError::from((
+                ErrorKind::ClientError,
+                "Broadcast is supported only in cluster mode",
+            )));
+        }
+        let nodes = self.cluster_node_addresses(primaries_only).await?;
+
+        let requests = nodes.iter().map(|(host, port)| {
+            let mut client = self.clone();
+            let routing = RoutingInfo::SingleNode(SingleNodeRoutingInfo::ByAddress {
+                host: host.clone(),
+                port: *port,
+            });
+            async move { client.send_command(cmd, Some(routing)).await }
+        });
+        let responses: Vec<Value> = match failure_policy {
+            BroadcastFailurePolicy::FailFast => try_join_all(requests).await?,
+            BroadcastFailurePolicy::CollectAll => join_all(requests)
+                .await
+                .into_iter()
+                .map(node_response)
+                .collect(),
+        };
+        Ok(Value::Map(
+            nodes
+                .into_iter()
+                .map(|(host, port)| Value::BulkString(format!("{host}:{port}").into_bytes()))
+                .zip(responses)
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_cluster_nodes;
+
+    const CLUSTER_NODES: &str = "\
+07c37dfeb235213a872192d90877d0cd55635b91 127.0.0.1:30004@31004,hostname4 slave e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 0 1426238317239 4 connected
+67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1 127.0.0.1:30002@31002,hostname2 master - 0 1426238316232 2 connected 5461-10922
+292f8b365bb7edb5e285caf0b7e6ddc7265d2f4f 127.0.0.1:30003@31003,,shard-id=69bc080733d1355567173199cff4a6a039a2f024 master - 0 1426238318243 3 connected 10923-16383
+6ec23923021cf3ffec47632106199cb7f496ce01 127.0.0.1:30005@31005,hostname5 slave,fail 67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1 0 1426238316232 5 connected
+e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 127.0.0.1:30001@31001 myself,master - 0 0 1 connected 0-5460
+";
+
+    #[test]
+    fn test_parse_cluster_nodes_skips_failed_nodes() {
+        assert_eq!(
+            parse_cluster_nodes(CLUSTER_NODES, false),
+            vec![
+                ("hostname4".to_string(), 30004),
+                ("hostname2".to_string(), 30002),
+                ("127.0.0.1".to_string(), 30003),
+                ("127.0.0.1".to_string(), 30001),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_cluster_nodes_primaries_only() {
+        assert_eq!(
+            parse_cluster_nodes(CLUSTER_NODES, true),
+            vec![
+                ("hostname2".to_string(), 30002),
+                ("127.0.0.1".to_string(), 30003),
+                ("127.0.0.1".to_string(), 30001),
+            ]
+        );
+    }
+}
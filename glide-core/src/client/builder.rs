@@ -0,0 +1,112 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::{
+    AuthenticationInfo, Client, ConnectionError, ConnectionRequest, ConnectionRetryStrategy,
+    NodeAddress, ReadFrom, TlsMode,
+};
+use redis::{PubSubSubscriptionInfo, PushInfo};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Builds a [`Client`] for direct use from Rust, without going through the socket protocol.
+///
+/// ```no_run
+/// # async fn example() -> redis::RedisResult<()> {
+/// let mut client = glide_core::client::ClientBuilder::new()
+///     .address("localhost", 6379)
+///     .request_timeout(std::time::Duration::from_millis(500))
+///     .build()
+///     .await
+///     .expect("Failed to connect");
+/// client.set("key", "value").await?;
+/// let value: Option<String> = client.get("key").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ClientBuilder {
+    request: ConnectionRequest,
+    push_sender: Option<mpsc::UnboundedSender<PushInfo>>,
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node address. In cluster mode, the addresses are used to discover the cluster's topology.
+    pub fn address(mut self, host: impl Into<String>, port: u16) -> Self {
+        self.request.addresses.push(NodeAddress {
+            host: host.into(),
+            port,
+        });
+        self
+    }
+
+    pub fn cluster_mode(mut self, cluster_mode_enabled: bool) -> Self {
+        self.request.cluster_mode_enabled = cluster_mode_enabled;
+        self
+    }
+
+    pub fn tls_mode(mut self, tls_mode: TlsMode) -> Self {
+        self.request.tls_mode = Some(tls_mode);
+        self
+    }
+
+    pub fn read_from(mut self, read_from: ReadFrom) -> Self {
+        self.request.read_from = Some(read_from);
+        self
+    }
+
+    pub fn credentials(mut self, username: Option<String>, password: Option<String>) -> Self {
+        self.request.authentication_info = Some(AuthenticationInfo { username, password });
+        self
+    }
+
+    pub fn client_name(mut self, client_name: impl Into<String>) -> Self {
+        self.request.client_name = Some(client_name.into());
+        self
+    }
+
+    pub fn database_id(mut self, database_id: i64) -> Self {
+        self.request.database_id = database_id;
+        self
+    }
+
+    pub fn protocol(mut self, protocol: redis::ProtocolVersion) -> Self {
+        self.request.protocol = Some(protocol);
+        self
+    }
+
+    pub fn request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request.request_timeout = Some(request_timeout.as_millis() as u32);
+        self
+    }
+
+    pub fn connection_retry_strategy(mut self, strategy: ConnectionRetryStrategy) -> Self {
+        self.request.connection_retry_strategy = Some(strategy);
+        self
+    }
+
+    /// Subscribes to the given channels on connection. Messages are sent to `push_sender`.
+    pub fn pubsub_subscriptions(
+        mut self,
+        subscriptions: PubSubSubscriptionInfo,
+        push_sender: mpsc::UnboundedSender<PushInfo>,
+    ) -> Self {
+        self.request.pubsub_subscriptions = Some(subscriptions);
+        self.push_sender = Some(push_sender);
+        self
+    }
+
+    /// Allows setting any option of the connection request that has no dedicated builder method.
+    pub fn configure(mut self, configure: impl FnOnce(&mut ConnectionRequest)) -> Self {
+        configure(&mut self.request);
+        self
+    }
+
+    pub async fn build(self) -> Result<Client, ConnectionError> {
+        Client::new(self.request, self.push_sender).await
+    }
+}
@@ -0,0 +1,245 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+// The server's capabilities, as reported by `HELLO` when a connection is established, so that features which depend
+// on the server's version or modules can be enabled only on servers that support them, without breaking older servers.
+use super::{run_with_timeout, Client, ClientWrapper};
+use redis::aio::MultiplexedConnection;
+use redis::cluster_routing::{RoutingInfo, SingleNodeRoutingInfo};
+use redis::{cmd, ErrorKind, RedisError, RedisResult, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerModule {
+    pub name: String,
+    pub version: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerCapabilities {
+    /// The server's name, such as `redis` or `valkey`.
+    pub server: String,
+    pub version: String,
+    pub protocol: i64,
+    /// `standalone`, `sentinel` or `cluster`.
+    pub mode: String,
+    pub modules: Vec<ServerModule>,
+}
+
+impl ServerCapabilities {
+    pub fn has_module(&self, name: &str) -> bool {
+        self.modules
+            .iter()
+            .any(|module| module.name.eq_ignore_ascii_case(name))
+    }
+
+    fn report(&self) -> Value {
+        let string = |string: &str| Value::SimpleString(string.to_string());
+        Value::Map(vec![
+            (string("server"), string(&self.server)),
+            (string("version"), string(&self.version)),
+            (string("proto"), Value::Int(self.protocol)),
+            (string("mode"), string(&self.mode)),
+            (
+                string("modules"),
+                Value::Map(
+                    self.modules
+                        .iter()
+                        .map(|module| (string(&module.name), Value::Int(module.version)))
+                        .collect(),
+                ),
+            ),
+        ])
+    }
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::BulkString(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        Value::SimpleString(string) => Some(string.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the fields of a map, which is a flat array of fields and values in RESP2.
+fn fields(value: Value) -> Option<Vec<(Value, Value)>> {
+    match value {
+        Value::Map(fields) => Some(fields),
+        Value::Array(values) => {
+            let mut values = values.into_iter();
+            Some(std::iter::from_fn(|| Some((values.next()?, values.next()?))).collect())
+        }
+        _ => None,
+    }
+}
+
+fn take_field(fields: &mut Vec<(Value, Value)>, name: &str) -> Option<Value> {
+    let index = fields
+        .iter()
+        .position(|(key, _)| value_to_string(key).as_deref() == Some(name))?;
+    Some(fields.swap_remove(index).1)
+}
+
+/// Parses the output of `HELLO`, which is converted to a map by the client.
+fn parse_hello_response(value: Value) -> RedisResult<ServerCapabilities> {
+    let invalid_response = || {
+        RedisError::from((
+            ErrorKind::TypeError,
+            "Unexpected HELLO response",
+            format!("{value:?}"),
+        ))
+    };
+    let mut hello = fields(value.clone()).ok_or_else(invalid_response)?;
+    let mut string_field = |name: &str| {
+        take_field(&mut hello, name)
+            .as_ref()
+            .and_then(value_to_string)
+            .ok_or_else(invalid_response)
+    };
+    let server = string_field("server")?;
+    let version = string_field("version")?;
+    let mode = string_field("mode")?;
+    let Some(Value::Int(protocol)) = take_field(&mut hello, "proto") else {
+        return Err(invalid_response());
+    };
+    let modules = match take_field(&mut hello, "modules") {
+        Some(Value::Array(modules)) => modules
+            .into_iter()
+            .map(|module| {
+                let mut module = fields(module)?;
+                let name = take_field(&mut module, "name")
+                    .as_ref()
+                    .and_then(value_to_string)?;
+                let Some(Value::Int(version)) = take_field(&mut module, "ver") else {
+                    return None;
+                };
+                Some(ServerModule { name, version })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid_response)?,
+        Some(Value::Nil) | None => Vec::new(),
+        Some(_) => return Err(invalid_response()),
+    };
+    Ok(ServerCapabilities {
+        server,
+        version,
+        protocol,
+        mode,
+        modules,
+    })
+}
+
+/// Queries the server's capabilities on the connection. Servers older than 6.0 don't support `HELLO`.
+pub(super) async fn query_server_capabilities(
+    connection: &mut MultiplexedConnection,
+) -> RedisResult<ServerCapabilities> {
+    connection
+        .send_packed_command(&cmd("HELLO"))
+        .await
+        .and_then(parse_hello_response)
+}
+
+impl Client {
+    /// Queries the server's capabilities, records them, and returns them. Standalone clients query the primary's
+    /// connection, and cluster clients query a random node through the cluster connection. Neither goes through the
+    /// user's command path, so the query isn't counted in the client's statistics or concurrency limits.
+    pub(super) async fn record_server_capabilities(&mut self) -> RedisResult<ServerCapabilities> {
+        let request_timeout = self.request_timeout();
+        match &mut self.internal_client {
+            ClientWrapper::Standalone(client) => client.refresh_server_capabilities().await,
+            ClientWrapper::Cluster { client } => {
+                let result = run_with_timeout(
+                    Some(request_timeout),
+                    client.route_command(
+                        &cmd("HELLO"),
+                        RoutingInfo::SingleNode(SingleNodeRoutingInfo::Random),
+                    ),
+                )
+                .await
+                .and_then(parse_hello_response);
+                *self.server_capabilities.write().unwrap() = result.as_ref().ok().cloned();
+                result
+            }
+        }
+    }
+
+    /// Returns the recorded capabilities of the server, or None if they're unknown. Standalone clients report the
+    /// capabilities negotiated by the primary's connection when it was established.
+    pub fn server_capabilities(&self) -> Option<ServerCapabilities> {
+        match &self.internal_client {
+            ClientWrapper::Standalone(client) => client.server_capabilities(),
+            ClientWrapper::Cluster { .. } => self.server_capabilities.read().unwrap().clone(),
+        }
+    }
+
+    /// Returns the recorded capabilities of the server as a map, or nil if they're unknown. If `refresh` is set, the
+    /// capabilities are queried again first, for example after the server was upgraded.
+    pub async fn server_capabilities_report(&mut self, refresh: bool) -> RedisResult<Value> {
+        if refresh {
+            return self
+                .record_server_capabilities()
+                .await
+                .map(|capabilities| capabilities.report());
+        }
+        Ok(self
+            .server_capabilities()
+            .map_or(Value::Nil, |capabilities| capabilities.report()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_hello_response, ServerModule};
+    use redis::Value;
+
+    fn bulk(string: &str) -> Value {
+        Value::BulkString(string.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_hello_response() {
+        let search_module = Value::Array(vec![
+            bulk("name"),
+            bulk("search"),
+            bulk("ver"),
+            Value::Int(20814),
+            bulk("path"),
+            bulk("/usr/lib/redis/modules/redisearch.so"),
+            bulk("args"),
+            Value::Array(vec![]),
+        ]);
+        let capabilities = parse_hello_response(Value::Map(vec![
+            (bulk("server"), bulk("valkey")),
+            (bulk("version"), bulk("7.2.5")),
+            (bulk("proto"), Value::Int(3)),
+            (bulk("id"), Value::Int(5)),
+            (bulk("mode"), bulk("cluster")),
+            (bulk("role"), bulk("master")),
+            (bulk("modules"), Value::Array(vec![search_module])),
+        ]))
+        .unwrap();
+        assert_eq!(
+            (
+                capabilities.server.as_str(),
+                capabilities.version.as_str(),
+                capabilities.protocol,
+                capabilities.mode.as_str()
+            ),
+            ("valkey", "7.2.5", 3, "cluster")
+        );
+        assert_eq!(
+            capabilities.modules,
+            vec![ServerModule {
+                name: "search".to_string(),
+                version: 20814
+            }]
+        );
+        assert!(capabilities.has_module("SEARCH"));
+        assert!(!capabilities.has_module("json"));
+    }
+
+    #[test]
+    fn test_parse_hello_response_rejects_missing_fields() {
+        assert!(parse_hello_response(Value::Map(vec![(bulk("server"), bulk("redis"))])).is_err());
+        assert!(parse_hello_response(Value::Nil).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::Client;
+use redis::{cmd, ErrorKind, RedisError, RedisResult, Value};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The serialization format of values stored with `set_encoded` and read with `get_decoded`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    Json,
+    MessagePack,
+    Bincode,
+}
+
+fn codec_error(operation: &'static str, err: impl std::fmt::Display) -> RedisError {
+    RedisError::from((ErrorKind::TypeError, operation, err.to_string()))
+}
+
+impl Codec {
+    pub fn encode<T: Serialize + ?Sized>(&self, value: &T) -> RedisResult<Vec<u8>> {
+        match self {
+            Codec::Json => {
+                serde_json::to_vec(value).map_err(|err| codec_error("Failed encoding JSON", err))
+            }
+            Codec::MessagePack => rmp_serde::to_vec(value)
+                .map_err(|err| codec_error("Failed encoding MessagePack", err)),
+            Codec::Bincode => {
+                bincode::serialize(value).map_err(|err| codec_error("Failed encoding Bincode", err))
+            }
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> RedisResult<T> {
+        match self {
+            Codec::Json => serde_json::from_slice(bytes)
+                .map_err(|err| codec_error("Failed decoding JSON", err)),
+            Codec::MessagePack => rmp_serde::from_slice(bytes)
+                .map_err(|err| codec_error("Failed decoding MessagePack", err)),
+            Codec::Bincode => bincode::deserialize(bytes)
+                .map_err(|err| codec_error("Failed decoding Bincode", err)),
+        }
+    }
+}
+
+impl Client {
+    /// Serializes the value with the codec and stores it in the key.
+    pub async fn set_encoded<T: Serialize + ?Sized>(
+        &mut self,
+        key: &[u8],
+        value: &T,
+        codec: Codec,
+    ) -> RedisResult<()> {
+        let mut set = cmd("SET");
+        set.arg(key).arg(codec.encode(value)?);
+        self.send_command(&set, None).await.map(|_| ())
+    }
+
+    /// Reads the key and deserializes its value with the codec. Returns `None` if the key doesn't exist.
+    pub async fn get_decoded<T: DeserializeOwned>(
+        &mut self,
+        key: &[u8],
+        codec: Codec,
+    ) -> RedisResult<Option<T>> {
+        let mut get = cmd("GET");
+        get.arg(key);
+        match self.send_command(&get, None).await? {
+            Value::Nil => Ok(None),
+            Value::BulkString(bytes) => codec.decode(&bytes).map(Some),
+            other => Err(codec_error(
+                "Unexpected response type",
+                format!("{other:?}"),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Codec;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_codecs_round_trip() {
+        let value = HashMap::from([("key".to_string(), vec![1u32, 2, 3])]);
+        for codec in [Codec::Json, Codec::MessagePack, Codec::Bincode] {
+            let encoded = codec.encode(&value).unwrap();
+            let decoded: HashMap<String, Vec<u32>> = codec.decode(&encoded).unwrap();
+            assert_eq!(decoded, value);
+        }
+    }
+
+    #[test]
+    fn test_decoding_invalid_value_fails() {
+        assert!(Codec::Json.decode::<Vec<u32>>(b"not json").is_err());
+    }
+}
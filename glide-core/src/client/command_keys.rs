@@ -0,0 +1,258 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+// The positions of commands' keys in their arguments. Whether a command has keys is decided by the cluster routing of
+// redis-rs, which sends keyless commands, such as `CONFIG SET`, to all nodes or to a random node. The keys of most
+// commands follow the command name, and the commands whose keys are elsewhere are listed here.
+use redis::cluster_routing::{MultipleNodeRoutingInfo, RoutingInfo, SingleNodeRoutingInfo};
+use redis::Cmd;
+
+/// The positions of a command's keys in its arguments.
+enum KeyPositions {
+    /// `count` keys, starting at the index.
+    Fixed { first: usize, count: usize },
+    /// Every `step`th argument from the index to the end, except for the last `trailing` arguments.
+    Range {
+        first: usize,
+        step: usize,
+        trailing: usize,
+    },
+    /// The number of keys is the argument at the index, and the keys follow it. Commands that store their result
+    /// have a destination key before it.
+    NumKeys { index: usize, destination: bool },
+    /// The keys follow the `STREAMS` argument, and are followed by as many IDs.
+    Streams,
+}
+
+/// Returns the positions of the command's keys, or None if the command has no keys.
+fn key_positions(cmd: &Cmd) -> Option<KeyPositions> {
+    let is_keyless = match RoutingInfo::for_routable(cmd) {
+        Some(RoutingInfo::SingleNode(SingleNodeRoutingInfo::SpecificNode(_))) => false,
+        Some(RoutingInfo::MultiNode((routing, _))) => matches!(
+            routing,
+            MultipleNodeRoutingInfo::AllNodes | MultipleNodeRoutingInfo::AllMasters
+        ),
+        _ => true,
+    };
+    if is_keyless {
+        return None;
+    }
+    let name = cmd.arg_idx(0)?.to_ascii_uppercase();
+    let num_keys = |index| KeyPositions::NumKeys {
+        index,
+        destination: false,
+    };
+    let range = |first, step, trailing| KeyPositions::Range {
+        first,
+        step,
+        trailing,
+    };
+    Some(match name.as_slice() {
+        b"EVAL" | b"EVALSHA" | b"EVAL_RO" | b"EVALSHA_RO" | b"FCALL" | b"FCALL_RO" | b"BLMPOP"
+        | b"BZMPOP" => num_keys(2),
+        b"LMPOP" | b"ZMPOP" | b"SINTERCARD" | b"ZINTERCARD" | b"ZUNION" | b"ZINTER" | b"ZDIFF" => {
+            num_keys(1)
+        }
+        b"ZUNIONSTORE" | b"ZINTERSTORE" | b"ZDIFFSTORE" => KeyPositions::NumKeys {
+            index: 2,
+            destination: true,
+        },
+        b"XREAD" | b"XREADGROUP" => KeyPositions::Streams,
+        b"MGET" | b"DEL" | b"EXISTS" | b"UNLINK" | b"TOUCH" | b"WATCH" | b"SUNION" | b"SINTER"
+        | b"SDIFF" | b"SUNIONSTORE" | b"SINTERSTORE" | b"SDIFFSTORE" | b"PFCOUNT" | b"PFMERGE" => {
+            range(1, 1, 0)
+        }
+        b"MSET" | b"MSETNX" => range(1, 2, 0),
+        b"BLPOP" | b"BRPOP" | b"BZPOPMIN" | b"BZPOPMAX" => range(1, 1, 1),
+        b"BITOP" => range(2, 1, 0),
+        b"RENAME" | b"RENAMENX" | b"COPY" | b"SMOVE" | b"LMOVE" | b"BLMOVE" | b"RPOPLPUSH"
+        | b"BRPOPLPUSH" | b"LCS" | b"ZRANGESTORE" | b"GEOSEARCHSTORE" => {
+            KeyPositions::Fixed { first: 1, count: 2 }
+        }
+        b"XGROUP" | b"XINFO" | b"OBJECT" | b"MEMORY" => KeyPositions::Fixed { first: 2, count: 1 },
+        _ => KeyPositions::Fixed { first: 1, count: 1 },
+    })
+}
+
+/// Returns the index of the argument following `STREAMS`, which is the first key of `XREAD` and `XREADGROUP`.
+fn first_stream_index(cmd: &Cmd) -> Option<usize> {
+    (1..)
+        .map_while(|index| Some((index, cmd.arg_idx(index)?)))
+        .find(|(_, arg)| arg.eq_ignore_ascii_case(b"STREAMS"))
+        .map(|(index, _)| index + 1)
+}
+
+/// Returns the number of keys of a command whose number of keys is the argument at the index.
+fn num_keys(cmd: &Cmd, index: usize) -> Option<usize> {
+    std::str::from_utf8(cmd.arg_idx(index)?).ok()?.parse().ok()
+}
+
+/// Returns the first key of the command, or None if the command has no keys, such as `CONFIG SET`, or `EVALSHA`
+/// with no keys.
+pub(super) fn first_key(cmd: &Cmd) -> Option<&[u8]> {
+    let index = match key_positions(cmd)? {
+        KeyPositions::Fixed { first, .. } | KeyPositions::Range { first, .. } => first,
+        KeyPositions::NumKeys {
+            index,
+            destination: true,
+        } => index - 1,
+        KeyPositions::NumKeys {
+            index,
+            destination: false,
+        } => {
+            if num_keys(cmd, index)? == 0 {
+                return None;
+            }
+            index + 1
+        }
+        KeyPositions::Streams => first_stream_index(cmd)?,
+    };
+    cmd.arg_idx(index)
+}
+
+/// Returns all of the command's keys, or an empty list if the command has no keys.
+pub(super) fn command_keys(cmd: &Cmd) -> Vec<&[u8]> {
+    let Some(positions) = key_positions(cmd) else {
+        return Vec::new();
+    };
+    let arg_count = (0..).map_while(|index| cmd.arg_idx(index)).count();
+    let indices: Vec<usize> = match positions {
+        KeyPositions::Fixed { first, count } => (first..first + count).collect(),
+        KeyPositions::Range {
+            first,
+            step,
+            trailing,
+        } => (first..arg_count.saturating_sub(trailing))
+            .step_by(step)
+            .collect(),
+        KeyPositions::NumKeys { index, destination } => {
+            let keys = index + 1..index + 1 + num_keys(cmd, index).unwrap_or(0);
+            destination
+                .then_some(index - 1)
+                .into_iter()
+                .chain(keys)
+                .collect()
+        }
+        KeyPositions::Streams => match first_stream_index(cmd) {
+            Some(first) => (first..first + arg_count.saturating_sub(first) / 2).collect(),
+            None => Vec::new(),
+        },
+    };
+    indices
+        .into_iter()
+        .filter_map(|index| cmd.arg_idx(index))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{command_keys, first_key};
+    use redis::cmd;
+
+    #[test]
+    fn test_first_key_follows_command_name() {
+        let mut get = cmd("GET");
+        get.arg("foo");
+        assert_eq!(first_key(&get), Some(b"foo".as_slice()));
+
+        let mut xgroup = cmd("XGROUP");
+        xgroup.arg("CREATE").arg("stream").arg("group").arg("$");
+        assert_eq!(first_key(&xgroup), Some(b"stream".as_slice()));
+
+        let mut object = cmd("OBJECT");
+        object.arg("ENCODING").arg("foo");
+        assert_eq!(first_key(&object), Some(b"foo".as_slice()));
+    }
+
+    #[test]
+    fn test_first_key_of_evalsha_follows_numkeys() {
+        let mut evalsha = cmd("EVALSHA");
+        evalsha.arg("sha").arg(2).arg("foo").arg("bar").arg("arg");
+        assert_eq!(first_key(&evalsha), Some(b"foo".as_slice()));
+
+        let mut keyless_evalsha = cmd("EVALSHA");
+        keyless_evalsha.arg("sha").arg(0).arg("arg");
+        assert_eq!(first_key(&keyless_evalsha), None);
+
+        let mut lmpop = cmd("LMPOP");
+        lmpop.arg(2).arg("foo").arg("bar").arg("LEFT");
+        assert_eq!(first_key(&lmpop), Some(b"foo".as_slice()));
+    }
+
+    #[test]
+    fn test_first_key_of_xread_follows_streams() {
+        let mut xread = cmd("XREAD");
+        xread
+            .arg("COUNT")
+            .arg(10)
+            .arg("streams")
+            .arg("stream")
+            .arg("0-0");
+        assert_eq!(first_key(&xread), Some(b"stream".as_slice()));
+
+        let mut xreadgroup = cmd("XREADGROUP");
+        xreadgroup
+            .arg("GROUP")
+            .arg("group")
+            .arg("consumer")
+            .arg("STREAMS")
+            .arg("stream")
+            .arg(">");
+        assert_eq!(first_key(&xreadgroup), Some(b"stream".as_slice()));
+    }
+
+    #[test]
+    fn test_keyless_commands_have_no_first_key() {
+        let mut config_set = cmd("CONFIG");
+        config_set.arg("SET").arg("maxmemory").arg("100mb");
+        assert_eq!(first_key(&config_set), None);
+
+        let mut client_setname = cmd("CLIENT");
+        client_setname.arg("SETNAME").arg("name");
+        assert_eq!(first_key(&client_setname), None);
+
+        assert_eq!(first_key(&cmd("PING")), None);
+    }
+
+    #[test]
+    fn test_command_keys() {
+        let mut mset = cmd("MSET");
+        mset.arg("foo").arg(1).arg("bar").arg(2);
+        assert_eq!(command_keys(&mset), vec![b"foo".as_slice(), b"bar"]);
+
+        let mut blpop = cmd("BLPOP");
+        blpop.arg("foo").arg("bar").arg(0);
+        assert_eq!(command_keys(&blpop), vec![b"foo".as_slice(), b"bar"]);
+
+        let mut zunionstore = cmd("ZUNIONSTORE");
+        zunionstore
+            .arg("dest")
+            .arg(2)
+            .arg("foo")
+            .arg("bar")
+            .arg("WEIGHTS")
+            .arg(1)
+            .arg(2);
+        assert_eq!(
+            command_keys(&zunionstore),
+            vec![b"dest".as_slice(), b"foo", b"bar"]
+        );
+
+        let mut xread = cmd("XREAD");
+        xread
+            .arg("STREAMS")
+            .arg("foo")
+            .arg("bar")
+            .arg("0-0")
+            .arg("0-0");
+        assert_eq!(command_keys(&xread), vec![b"foo".as_slice(), b"bar"]);
+
+        let mut lmove = cmd("LMOVE");
+        lmove.arg("foo").arg("bar").arg("LEFT").arg("RIGHT");
+        assert_eq!(command_keys(&lmove), vec![b"foo".as_slice(), b"bar"]);
+
+        let mut config_set = cmd("CONFIG");
+        config_set.arg("SET").arg("maxmemory").arg("100mb");
+        assert!(command_keys(&config_set).is_empty());
+    }
+}
@@ -0,0 +1,112 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::Client;
+use redis::{cmd, Cmd, FromRedisValue, RedisResult, ToRedisArgs};
+use std::time::Duration;
+
+// Typed wrappers of common commands, for direct use from Rust. Other commands can be sent with `send_command`, and
+// batches with `send_transaction`.
+impl Client {
+    async fn send_typed<T: FromRedisValue>(&mut self, cmd: Cmd) -> RedisResult<T> {
+        let value = self.send_command(&cmd, None).await?;
+        redis::from_owned_redis_value(value)
+    }
+
+    pub async fn get<K: ToRedisArgs, T: FromRedisValue>(&mut self, key: K) -> RedisResult<T> {
+        let mut get = cmd("GET");
+        get.arg(key);
+        self.send_typed(get).await
+    }
+
+    pub async fn set<K: ToRedisArgs, V: ToRedisArgs>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> RedisResult<()> {
+        let mut set = cmd("SET");
+        set.arg(key).arg(value);
+        self.send_typed(set).await
+    }
+
+    pub async fn set_with_expiry<K: ToRedisArgs, V: ToRedisArgs>(
+        &mut self,
+        key: K,
+        value: V,
+        expiry: Duration,
+    ) -> RedisResult<()> {
+        let mut set = cmd("SET");
+        set.arg(key)
+            .arg(value)
+            .arg("PX")
+            .arg(expiry.as_millis() as u64);
+        self.send_typed(set).await
+    }
+
+    /// Returns the number of deleted keys. In cluster mode, the keys are deleted from all of their slots.
+    pub async fn del<K: ToRedisArgs>(&mut self, keys: K) -> RedisResult<i64> {
+        let mut del = cmd("DEL");
+        del.arg(keys);
+        self.send_typed(del).await
+    }
+
+    /// Returns the number of existing keys.
+    pub async fn exists<K: ToRedisArgs>(&mut self, keys: K) -> RedisResult<i64> {
+        let mut exists = cmd("EXISTS");
+        exists.arg(keys);
+        self.send_typed(exists).await
+    }
+
+    /// Returns whether the timeout was set.
+    pub async fn expire<K: ToRedisArgs>(&mut self, key: K, expiry: Duration) -> RedisResult<bool> {
+        let mut pexpire = cmd("PEXPIRE");
+        pexpire.arg(key).arg(expiry.as_millis() as u64);
+        self.send_typed(pexpire).await
+    }
+
+    /// Returns the value after the increment.
+    pub async fn incr_by<K: ToRedisArgs>(&mut self, key: K, delta: i64) -> RedisResult<i64> {
+        let mut incrby = cmd("INCRBY");
+        incrby.arg(key).arg(delta);
+        self.send_typed(incrby).await
+    }
+
+    pub async fn hget<K: ToRedisArgs, F: ToRedisArgs, T: FromRedisValue>(
+        &mut self,
+        key: K,
+        field: F,
+    ) -> RedisResult<T> {
+        let mut hget = cmd("HGET");
+        hget.arg(key).arg(field);
+        self.send_typed(hget).await
+    }
+
+    /// Returns the number of added fields.
+    pub async fn hset<K: ToRedisArgs, F: ToRedisArgs, V: ToRedisArgs>(
+        &mut self,
+        key: K,
+        field: F,
+        value: V,
+    ) -> RedisResult<i64> {
+        let mut hset = cmd("HSET");
+        hset.arg(key).arg(field).arg(value);
+        self.send_typed(hset).await
+    }
+
+    pub async fn hgetall<K: ToRedisArgs, T: FromRedisValue>(&mut self, key: K) -> RedisResult<T> {
+        let mut hgetall = cmd("HGETALL");
+        hgetall.arg(key);
+        self.send_typed(hgetall).await
+    }
+
+    /// Returns the number of clients that received the message.
+    pub async fn publish<C: ToRedisArgs, M: ToRedisArgs>(
+        &mut self,
+        channel: C,
+        message: M,
+    ) -> RedisResult<i64> {
+        let mut publish = cmd("PUBLISH");
+        publish.arg(channel).arg(message);
+        self.send_typed(publish).await
+    }
+}
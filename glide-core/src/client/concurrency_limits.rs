@@ -0,0 +1,89 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use redis::cluster_routing::Routable;
+use redis::Cmd;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Limits the number of concurrent requests of a group of commands. The time a request waits for the limit isn't
+/// counted against its timeout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandConcurrencyLimit {
+    /// The names of the commands sharing the limit, such as `SCAN` or `CONFIG GET`.
+    pub commands: Vec<String>,
+    pub max_concurrent_requests: u32,
+}
+
+/// Enforces the configured concurrency limits. Requests over the limit wait until a running request of the same group completes.
+pub(super) struct ConcurrencyLimiter {
+    semaphores: HashMap<Vec<u8>, Arc<Semaphore>>,
+}
+
+impl ConcurrencyLimiter {
+    pub(super) fn new(limits: &[CommandConcurrencyLimit]) -> Self {
+        let mut semaphores = HashMap::new();
+        for limit in limits {
+            // A zero limit is treated as unset, since it would block the commands forever.
+            if limit.max_concurrent_requests == 0 {
+                continue;
+            }
+            let semaphore = Arc::new(Semaphore::new(limit.max_concurrent_requests as usize));
+            for command in limit.commands.iter() {
+                semaphores.insert(command.to_ascii_uppercase().into_bytes(), semaphore.clone());
+            }
+        }
+        Self { semaphores }
+    }
+
+    /// Waits until the command may be sent. The returned permit should be held until the request completes.
+    /// Commands without a configured limit return immediately with no permit.
+    pub(super) async fn acquire(&self, cmd: &Cmd) -> Option<OwnedSemaphorePermit> {
+        let semaphore = self.semaphores.get(&cmd.command()?)?.clone();
+        // The semaphores are never closed, so acquiring can't fail.
+        semaphore.acquire_owned().await.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandConcurrencyLimit, ConcurrencyLimiter};
+    use redis::cmd;
+
+    #[tokio::test]
+    async fn test_limit_is_shared_by_the_commands_in_a_group() {
+        let limiter = ConcurrencyLimiter::new(&[CommandConcurrencyLimit {
+            commands: vec!["scan".to_string(), "SSCAN".to_string()],
+            max_concurrent_requests: 1,
+        }]);
+
+        let mut scan = cmd("SCAN");
+        scan.arg(0);
+        let permit = limiter.acquire(&scan).await;
+        assert!(permit.is_some());
+
+        let mut sscan = cmd("SSCAN");
+        sscan.arg("key").arg(0);
+        let blocked = tokio::time::timeout(
+            std::time::Duration::from_millis(10),
+            limiter.acquire(&sscan),
+        )
+        .await;
+        assert!(blocked.is_err());
+
+        drop(permit);
+        assert!(limiter.acquire(&sscan).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_commands_without_limit_are_not_blocked() {
+        let limiter = ConcurrencyLimiter::new(&[CommandConcurrencyLimit {
+            commands: vec!["DUMP".to_string()],
+            max_concurrent_requests: 1,
+        }]);
+        let mut get = cmd("GET");
+        get.arg("key");
+        assert!(limiter.acquire(&get).await.is_none());
+    }
+}
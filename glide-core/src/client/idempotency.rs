@@ -0,0 +1,33 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::Client;
+use redis::{cmd, RedisResult, Value};
+use std::time::Duration;
+
+impl Client {
+    /// Registers the idempotency key for the given TTL, and returns whether it wasn't registered before.
+    /// Consumers should only process a message if this returns true, so that redelivered messages are processed once.
+    /// The check and the registration are a single `SET NX`, so concurrent consumers can't both succeed.
+    pub async fn idempotency_check_and_set(
+        &mut self,
+        key: &[u8],
+        ttl: Duration,
+    ) -> RedisResult<Value> {
+        let mut set = cmd("SET");
+        set.arg(key)
+            .arg(1)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis() as u64);
+        let result = self.send_command(&set, None).await?;
+        Ok(Value::Boolean(result == Value::Okay))
+    }
+
+    /// Removes the idempotency key, for example when processing failed and the message should be processed again.
+    pub async fn idempotency_release(&mut self, key: &[u8]) -> RedisResult<Value> {
+        let mut del = cmd("DEL");
+        del.arg(key);
+        self.send_command(&del, None).await
+    }
+}
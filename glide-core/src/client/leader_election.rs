@@ -0,0 +1,122 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+// Leader election over a single key, holding the leader's name with a TTL.
+// The leader has to renew its leadership before the TTL expires - a renewal interval of a third of the TTL is
+// recommended. Other candidates observe leader changes by polling the current leader.
+use super::Client;
+use crate::scripts_container::add_script;
+use once_cell::sync::Lazy;
+use redis::{cmd, RedisResult, Value};
+use std::time::Duration;
+
+static CAMPAIGN_SCRIPT: Lazy<String> = Lazy::new(|| {
+    add_script(
+        r#"
+local current = redis.call('GET', KEYS[1])
+if current == ARGV[1] then
+    redis.call('PEXPIRE', KEYS[1], ARGV[2])
+    return 1
+end
+if current then
+    return 0
+end
+redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+return 1
+"#,
+    )
+});
+
+static RENEW_SCRIPT: Lazy<String> = Lazy::new(|| {
+    add_script(
+        r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    redis.call('PEXPIRE', KEYS[1], ARGV[2])
+    return 1
+end
+return 0
+"#,
+    )
+});
+
+static RESIGN_SCRIPT: Lazy<String> = Lazy::new(|| {
+    add_script(
+        r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    redis.call('DEL', KEYS[1])
+    return 1
+end
+return 0
+"#,
+    )
+});
+
+fn to_boolean(value: Value) -> Value {
+    Value::Boolean(value == Value::Int(1))
+}
+
+impl Client {
+    /// Tries to become the leader, or extends the leadership if the candidate is already the leader.
+    /// Returns whether the candidate is the leader.
+    pub async fn leader_campaign(
+        &mut self,
+        key: &str,
+        candidate: &str,
+        ttl: Duration,
+    ) -> RedisResult<Value> {
+        let ttl = ttl.as_millis().to_string();
+        self.invoke_script(
+            &CAMPAIGN_SCRIPT,
+            vec![key],
+            vec![candidate, ttl.as_str()],
+            None,
+        )
+        .await
+        .map(to_boolean)
+    }
+
+    /// Extends the leadership of the candidate. Returns false if the candidate is no longer the leader.
+    pub async fn leader_renew(
+        &mut self,
+        key: &str,
+        candidate: &str,
+        ttl: Duration,
+    ) -> RedisResult<Value> {
+        let ttl = ttl.as_millis().to_string();
+        self.invoke_script(
+            &RENEW_SCRIPT,
+            vec![key],
+            vec![candidate, ttl.as_str()],
+            None,
+        )
+        .await
+        .map(to_boolean)
+    }
+
+    /// Gives up the leadership of the candidate. Returns false if the candidate wasn't the leader.
+    pub async fn leader_resign(&mut self, key: &str, candidate: &str) -> RedisResult<Value> {
+        self.invoke_script(&RESIGN_SCRIPT, vec![key], vec![candidate], None)
+            .await
+            .map(to_boolean)
+    }
+
+    /// Returns the name of the current leader, or nil if there's no leader.
+    pub async fn leader(&mut self, key: &str) -> RedisResult<Value> {
+        let mut get = cmd("GET");
+        get.arg(key);
+        self.send_command(&get, None).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CAMPAIGN_SCRIPT, RENEW_SCRIPT, RESIGN_SCRIPT};
+    use crate::scripts_container::get_script;
+
+    #[test]
+    fn test_scripts_are_registered() {
+        for hash in [&*CAMPAIGN_SCRIPT, &*RENEW_SCRIPT, &*RESIGN_SCRIPT] {
+            assert!(get_script(hash).is_some());
+        }
+    }
+}
@@ -5,7 +5,7 @@ mod types;
 
 use crate::scripts_container::get_script;
 use futures::FutureExt;
-use logger_core::log_info;
+use logger_core::{log_info, log_warn};
 use redis::aio::ConnectionLike;
 use redis::cluster_async::ClusterConnection;
 use redis::cluster_routing::{Routable, RoutingInfo, SingleNodeRoutingInfo};
@@ -18,16 +18,66 @@ use std::time::Duration;
 pub use types::*;
 
 use self::value_conversion::{convert_to_expected_type, expected_type_for_cmd, get_value_type};
+mod batch_splitter;
+pub use batch_splitter::{BatchPlan, SubBatch};
+mod broadcast;
+pub use broadcast::BroadcastFailurePolicy;
+mod builder;
+pub use builder::ClientBuilder;
+mod capabilities;
+pub use capabilities::{ServerCapabilities, ServerModule};
+#[cfg(feature = "codecs")]
+mod codec;
+#[cfg(feature = "codecs")]
+pub use codec::Codec;
+mod command_keys;
+mod commands;
+mod concurrency_limits;
+pub use concurrency_limits::CommandConcurrencyLimit;
+use concurrency_limits::ConcurrencyLimiter;
+mod idempotency;
+mod leader_election;
+mod namespaced_client;
+pub use namespaced_client::NamespacedClient;
+mod pinning;
+mod profiles;
+mod queue;
 mod reconnecting_connection;
+mod scheduler;
+use scheduler::CommandScheduler;
+pub use scheduler::ScheduledTime;
+mod sentinel;
+mod settings;
+pub use settings::ClientSettingsUpdate;
+mod shadowing_client;
+pub use shadowing_client::ShadowingClient;
+mod slot_availability;
+use slot_availability::SlotAvailability;
 mod standalone_client;
+mod statistics;
+mod subscriptions;
 mod value_conversion;
+pub use statistics::{BigKeyDetection, HotKeyDetection};
+use statistics::{BigKeyTracker, HotKeySketch, PayloadSizeHistograms};
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, RwLock};
+use subscriptions::SubscriptionTracker;
 use tokio::sync::mpsc;
+#[cfg(fuzzing)]
+pub use value_conversion::convert_response;
 
 pub const HEARTBEAT_SLEEP_DURATION: Duration = Duration::from_secs(1);
+/// The kind of the push notification sent when a standalone connection starts reconnecting. Messages published until
+/// the connection is restored are lost, so the notification marks where a gap in the messages may start.
+pub const RECONNECTION_PUSH_KIND: &str = "Reconnection";
 
 pub const DEFAULT_RESPONSE_TIMEOUT: Duration = Duration::from_millis(250);
 pub const DEFAULT_CONNECTION_ATTEMPT_TIMEOUT: Duration = Duration::from_millis(250);
 pub const DEFAULT_PERIODIC_CHECKS_INTERVAL: Duration = Duration::from_secs(60);
+pub const DEFAULT_REPLICA_LAG_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+pub const DEFAULT_BIG_KEYS_REPORT_SIZE: usize = 32;
+pub const DEFAULT_HOT_KEYS_REPORT_SIZE: usize = 32;
+pub const DEFAULT_QUEUE_VISIBILITY_TIMEOUT: Duration = Duration::from_secs(30);
 pub const INTERNAL_CONNECTION_TIMEOUT: Duration = Duration::from_millis(250);
 
 pub(super) fn get_port(address: &NodeAddress) -> u16 {
@@ -95,7 +145,21 @@ pub enum ClientWrapper {
 #[derive(Clone)]
 pub struct Client {
     internal_client: ClientWrapper,
-    request_timeout: Duration,
+    /// The request timeout in milliseconds, shared by the client's clones so that it can be updated on a live client.
+    request_timeout: Arc<AtomicU64>,
+    /// Extension to the timeout of blocking commands, in seconds.
+    blocking_timeout_extension: f64,
+    big_keys: Option<Arc<BigKeyTracker>>,
+    hot_keys: Option<Arc<HotKeySketch>>,
+    payload_sizes: Option<Arc<PayloadSizeHistograms>>,
+    concurrency_limiter: Arc<ConcurrencyLimiter>,
+    subscriptions: Option<Arc<SubscriptionTracker>>,
+    /// Set when commands to the slots of failed primaries should fail fast.
+    slot_availability: Option<Arc<SlotAvailability>>,
+    scheduler: Arc<CommandScheduler>,
+    /// The capabilities of a random cluster node, recorded when the client is created. None if they're unknown.
+    /// Standalone clients record the capabilities of each connection instead.
+    server_capabilities: Arc<RwLock<Option<ServerCapabilities>>>,
 }
 
 async fn run_with_timeout<T>(
@@ -111,7 +175,7 @@ async fn run_with_timeout<T>(
     }
 }
 
-/// Extension to the request timeout for blocking commands to ensure we won't return with timeout error before the server responded
+/// Default extension to the request timeout for blocking commands to ensure we won't return with timeout error before the server responded
 const BLOCKING_CMD_TIMEOUT_EXTENSION: f64 = 0.5; // seconds
 
 enum TimeUnit {
@@ -158,10 +222,12 @@ fn parse_timeout_to_f64(cmd: &Cmd, timeout_idx: usize) -> RedisResult<f64> {
 /// Attempts to get the timeout duration from the command argument at `timeout_idx`.
 /// If the argument can be parsed into a duration, it returns the duration in seconds with BlockingCmdTimeout.
 /// If the timeout argument value is zero, NoTimeout will be returned. Otherwise, ClientConfigTimeout is returned.
+/// The blocking command's timeout is extended by `timeout_extension` seconds.
 fn get_timeout_from_cmd_arg(
     cmd: &Cmd,
     timeout_idx: usize,
     time_unit: TimeUnit,
+    timeout_extension: f64,
 ) -> RedisResult<RequestTimeoutOption> {
     let timeout_secs = parse_timeout_to_f64(cmd, timeout_idx)? / ((time_unit as i32) as f64);
     if timeout_secs < 0.0 {
@@ -185,26 +251,32 @@ fn get_timeout_from_cmd_arg(
         } else {
             // Extend the request timeout to ensure we don't timeout before receiving a response from the server.
             Ok(RequestTimeoutOption::BlockingCommand(
-                Duration::from_secs_f64(
-                    (timeout_secs + BLOCKING_CMD_TIMEOUT_EXTENSION).min(u32::MAX as f64),
-                ),
+                Duration::from_secs_f64((timeout_secs + timeout_extension).min(u32::MAX as f64)),
             ))
         }
     }
 }
 
-fn get_request_timeout(cmd: &Cmd, default_timeout: Duration) -> RedisResult<Option<Duration>> {
+fn get_request_timeout(
+    cmd: &Cmd,
+    default_timeout: Duration,
+    blocking_timeout_extension: f64,
+) -> RedisResult<Option<Duration>> {
     let command = cmd.command().unwrap_or_default();
+    let timeout_from_arg = |timeout_idx, time_unit| {
+        get_timeout_from_cmd_arg(cmd, timeout_idx, time_unit, blocking_timeout_extension)
+    };
     let timeout = match command.as_slice() {
         b"BLPOP" | b"BRPOP" | b"BLMOVE" | b"BZPOPMAX" | b"BZPOPMIN" | b"BRPOPLPUSH" => {
-            get_timeout_from_cmd_arg(cmd, cmd.args_iter().len() - 1, TimeUnit::Seconds)
+            timeout_from_arg(cmd.args_iter().len() - 1, TimeUnit::Seconds)
         }
-        b"BLMPOP" | b"BZMPOP" => get_timeout_from_cmd_arg(cmd, 1, TimeUnit::Seconds),
+        b"BLMPOP" | b"BZMPOP" => timeout_from_arg(1, TimeUnit::Seconds),
         b"XREAD" | b"XREADGROUP" => cmd
             .position(b"BLOCK")
-            .map(|idx| get_timeout_from_cmd_arg(cmd, idx + 1, TimeUnit::Milliseconds))
+            .map(|idx| timeout_from_arg(idx + 1, TimeUnit::Milliseconds))
             .unwrap_or(Ok(RequestTimeoutOption::ClientConfig)),
-        b"WAIT" => get_timeout_from_cmd_arg(cmd, 2, TimeUnit::Milliseconds),
+        b"WAIT" => timeout_from_arg(2, TimeUnit::Milliseconds),
+        b"WAITAOF" => timeout_from_arg(3, TimeUnit::Milliseconds),
         _ => Ok(RequestTimeoutOption::ClientConfig),
     }?;
 
@@ -224,25 +296,46 @@ impl Client {
         routing: Option<RoutingInfo>,
     ) -> redis::RedisFuture<'a, Value> {
         let expected_type = expected_type_for_cmd(cmd);
-        let request_timeout = match get_request_timeout(cmd, self.request_timeout) {
-            Ok(request_timeout) => request_timeout,
-            Err(err) => {
-                return async { Err(err) }.boxed();
-            }
-        };
-        run_with_timeout(request_timeout, async move {
-            match self.internal_client {
-                ClientWrapper::Standalone(ref mut client) => client.send_command(cmd).await,
-
-                ClientWrapper::Cluster { ref mut client } => {
-                    let routing = routing
-                        .or_else(|| RoutingInfo::for_routable(cmd))
-                        .unwrap_or(RoutingInfo::SingleNode(SingleNodeRoutingInfo::Random));
-                    client.route_command(cmd, routing).await
+        let request_timeout =
+            match get_request_timeout(cmd, self.request_timeout(), self.blocking_timeout_extension)
+            {
+                Ok(request_timeout) => request_timeout,
+                Err(err) => {
+                    return async { Err(err) }.boxed();
                 }
-            }
-            .and_then(|value| convert_to_expected_type(value, expected_type))
-        })
+            };
+        if let Some(hot_keys) = &self.hot_keys {
+            hot_keys.record(cmd);
+        }
+        self.refresh_slot_availability_if_stale();
+        async move {
+            // The permit is acquired before the request's timeout starts, so that waiting for it isn't reported as a
+            // timeout of the server.
+            let _permit = self.concurrency_limiter.acquire(cmd).await;
+            run_with_timeout(request_timeout, async move {
+                let result = match self.internal_client {
+                    ClientWrapper::Standalone(ref mut client) => client.send_command(cmd).await,
+
+                    ClientWrapper::Cluster { ref mut client } => {
+                        let routing = routing
+                            .or_else(|| RoutingInfo::for_routable(cmd))
+                            .unwrap_or(RoutingInfo::SingleNode(SingleNodeRoutingInfo::Random));
+                        if let Some(slot_availability) = &self.slot_availability {
+                            slot_availability.check(&routing)?;
+                        }
+                        client.route_command(cmd, routing).await
+                    }
+                };
+                if let (Ok(value), Some(big_keys)) = (&result, &self.big_keys) {
+                    big_keys.record(cmd, value);
+                }
+                if let Some(payload_sizes) = &self.payload_sizes {
+                    payload_sizes.record(cmd, result.as_ref().ok());
+                }
+                result.and_then(|value| convert_to_expected_type(value, expected_type))
+            })
+            .await
+        }
         .boxed()
     }
 
@@ -308,7 +401,7 @@ impl Client {
     ) -> redis::RedisFuture<'a, Value> {
         let command_count = pipeline.cmd_iter().count();
         let offset = command_count + 1;
-        run_with_timeout(Some(self.request_timeout), async move {
+        run_with_timeout(Some(self.request_timeout()), async move {
             let values = match self.internal_client {
                 ClientWrapper::Standalone(ref mut client) => {
                     client.send_pipeline(pipeline, offset, 1).await
@@ -327,6 +420,58 @@ impl Client {
         .boxed()
     }
 
+    /// Returns a snapshot of the client's statistics, as a map from the statistic's name to its value.
+    pub fn statistics(&self) -> Value {
+        let mut statistics = Vec::new();
+        if let Some(big_keys) = &self.big_keys {
+            statistics.push((
+                Value::SimpleString("big_keys".to_string()),
+                big_keys.report(),
+            ));
+        }
+        if let Some(hot_keys) = &self.hot_keys {
+            statistics.push((
+                Value::SimpleString("hot_keys".to_string()),
+                hot_keys.report(),
+            ));
+        }
+        if let Some(payload_sizes) = &self.payload_sizes {
+            statistics.push((
+                Value::SimpleString("payload_sizes".to_string()),
+                payload_sizes.report(),
+            ));
+        }
+        if let ClientWrapper::Standalone(client) = &self.internal_client {
+            statistics.push((
+                Value::SimpleString("nodes".to_string()),
+                client.node_statistics(),
+            ));
+        }
+        if let Some(subscriptions) = &self.subscriptions {
+            statistics.push((
+                Value::SimpleString("subscriptions".to_string()),
+                subscriptions.report(),
+            ));
+        }
+        Value::Map(statistics)
+    }
+
+    /// Completes once all of the subscriptions configured in the connection request are confirmed by the server,
+    /// including resubscriptions after a reconnect. Fails if that doesn't happen within the given timeout.
+    pub async fn wait_for_subscriptions(&self, timeout: Option<Duration>) -> RedisResult<()> {
+        let Some(subscriptions) = self.subscriptions.clone() else {
+            return Ok(());
+        };
+        run_with_timeout(
+            Some(timeout.unwrap_or(self.request_timeout())),
+            async move {
+                subscriptions.wait_for_subscriptions().await;
+                Ok(())
+            },
+        )
+        .await
+    }
+
     pub async fn invoke_script<'a, T: Deref<Target = str>>(
         &'a mut self,
         hash: &'a str,
@@ -388,7 +533,29 @@ async fn create_cluster_client(
         .into_iter()
         .map(|address| get_connection_info(&address, tls_mode, redis_connection_info.clone()))
         .collect();
-    let read_from = request.read_from.unwrap_or_default();
+    if request.replica_lag_check.is_some() {
+        log_warn(
+            "client creation",
+            "Replica lag checks are supported only in standalone mode, ignoring the configuration",
+        );
+    }
+    if request
+        .replica_selection
+        .as_ref()
+        .is_some_and(|replica_selection| *replica_selection != ReplicaSelection::RoundRobin)
+    {
+        log_warn(
+            "client creation",
+            "Replica selection strategies are supported only in standalone mode, ignoring the configuration",
+        );
+    }
+    if request.subscriber_only {
+        log_warn(
+            "client creation",
+            "Subscriber only mode is supported only in standalone mode, ignoring the configuration",
+        );
+    }
+    let read_from = request.read_from.clone().unwrap_or_default();
     let read_from_replicas = !matches!(read_from, ReadFrom::Primary); // TODO - implement different read from replica strategies.
     let periodic_checks = match request.periodic_checks {
         Some(PeriodicCheck::Disabled) => None,
@@ -428,6 +595,8 @@ pub enum ConnectionError {
     Standalone(standalone_client::StandaloneClientConnectionError),
     Cluster(redis::RedisError),
     Timeout,
+    /// The connection request contains invalid or conflicting options. Each entry describes one problem.
+    InvalidConfiguration(Vec<String>),
 }
 
 impl std::fmt::Debug for ConnectionError {
@@ -436,6 +605,9 @@ impl std::fmt::Debug for ConnectionError {
             Self::Standalone(arg0) => f.debug_tuple("Standalone").field(arg0).finish(),
             Self::Cluster(arg0) => f.debug_tuple("Cluster").field(arg0).finish(),
             Self::Timeout => write!(f, "Timeout"),
+            Self::InvalidConfiguration(arg0) => {
+                f.debug_tuple("InvalidConfiguration").field(arg0).finish()
+            }
         }
     }
 }
@@ -446,8 +618,104 @@ impl std::fmt::Display for ConnectionError {
             ConnectionError::Standalone(err) => write!(f, "{err:?}"),
             ConnectionError::Cluster(err) => write!(f, "{err}"),
             ConnectionError::Timeout => f.write_str("connection attempt timed out"),
+            ConnectionError::InvalidConfiguration(errors) => {
+                write!(f, "invalid configuration: {}", errors.join("; "))
+            }
+        }
+    }
+}
+
+/// Returns the problems found in the connection request, which would otherwise cause a failure, or be silently
+/// ignored, after the client is created.
+fn validate_request(request: &ConnectionRequest) -> Vec<String> {
+    let mut errors = Vec::new();
+    if request.addresses.is_empty() {
+        errors.push("no addresses were provided".to_string());
+    }
+    for address in &request.addresses {
+        // A port of 0 isn't invalid - it's replaced by the default port.
+        if address.host.is_empty() {
+            errors.push(format!("invalid address `:{}`", address.port));
+        }
+    }
+    let reads_from_replicas = matches!(
+        request.read_from,
+        Some(ReadFrom::PreferReplica | ReadFrom::AZAffinity(_))
+    );
+    if !reads_from_replicas
+        && request
+            .replica_selection
+            .as_ref()
+            .is_some_and(|replica_selection| *replica_selection != ReplicaSelection::RoundRobin)
+    {
+        errors
+            .push("a replica selection strategy was set without reading from replicas".to_string());
+    }
+    if let Some(ReplicaSelection::Weighted(weights)) = &request.replica_selection {
+        if !request.cluster_mode_enabled
+            && request.sentinel_master_name.is_none()
+            && weights.len() != request.addresses.len()
+        {
+            errors.push(format!(
+                "weighted replica selection has {} weights for {} addresses",
+                weights.len(),
+                request.addresses.len()
+            ));
+        }
+    }
+    if request
+        .replica_lag_check
+        .as_ref()
+        .is_some_and(|replica_lag_check| replica_lag_check.interval.is_zero())
+    {
+        errors.push("the replica lag check interval must be positive".to_string());
+    }
+    if matches!(request.periodic_checks, Some(PeriodicCheck::ManualInterval(interval)) if interval.is_zero())
+    {
+        errors.push("the periodic checks interval must be positive".to_string());
+    }
+    if request.subscriber_only && request.pubsub_subscriptions.is_none() {
+        errors.push("subscriber only mode was set without pubsub subscriptions".to_string());
+    }
+    if request.sentinel_master_name.is_some() {
+        if request.cluster_mode_enabled {
+            errors.push("sentinel is supported only in standalone mode".to_string());
+        }
+        // The weights are matched with the addresses, which are replaced by the nodes discovered through the sentinels.
+        if matches!(
+            request.replica_selection,
+            Some(ReplicaSelection::Weighted(_))
+        ) {
+            errors.push("weighted replica selection isn't supported with sentinel".to_string());
+        }
+    }
+    if let Some(ReadFrom::AZAffinity(az)) = &request.read_from {
+        if request.cluster_mode_enabled {
+            errors.push("AZ affinity is supported only in standalone mode".to_string());
+        }
+        if az.is_empty() {
+            errors.push("AZ affinity requires the client's availability zone".to_string());
+        }
+    }
+    if request.fail_fast_on_unavailable_slots && !request.cluster_mode_enabled {
+        errors.push(
+            "failing fast on unavailable slots is supported only in cluster mode".to_string(),
+        );
+    }
+    if request.connections_per_node > 1 && request.cluster_mode_enabled {
+        errors.push(
+            "multiple connections per node are supported only in standalone mode".to_string(),
+        );
+    }
+    for limit in &request.command_concurrency_limits {
+        if limit.max_concurrent_requests == 0 {
+            errors.push(format!(
+                "the concurrency limit of {:?} must be positive",
+                limit.commands
+            ));
         }
     }
+    errors
 }
 
 fn format_optional_value<T>(name: &'static str, value: Option<T>) -> String
@@ -487,19 +755,61 @@ fn sanitized_request_string(request: &ConnectionRequest) -> String {
         "\nStandalone mode"
     };
     let request_timeout = format_optional_value("Request timeout", request.request_timeout);
+    let subscriber_only = if request.subscriber_only {
+        "\nSubscriber only"
+    } else {
+        ""
+    };
+    let payload_size_histograms = if request.payload_size_histograms {
+        "\nPayload size histograms"
+    } else {
+        ""
+    };
+    let fail_fast_on_unavailable_slots = if request.fail_fast_on_unavailable_slots {
+        "\nFail fast on unavailable slots"
+    } else {
+        ""
+    };
+    let connection_pool = if request.connections_per_node > 1 {
+        format!(
+            "\nConnections per node: {}, dispatch: {:?}",
+            request.connections_per_node, request.connection_pool_dispatch
+        )
+    } else {
+        String::new()
+    };
+    let configuration_profile = request
+        .configuration_profile
+        .map(|profile| format!("\nConfiguration profile: {profile:?}"))
+        .unwrap_or_default();
+    let sentinel_master_name = format_optional_value(
+        "Sentinel master name",
+        request.sentinel_master_name.as_ref(),
+    );
+    let blocking_command_timeout_extension = request
+        .blocking_command_timeout_extension
+        .map(|extension| format!("\nBlocking command timeout extension: {extension:?}"))
+        .unwrap_or_default();
     let database_id = format!("\ndatabase ID: {}", request.database_id);
     let rfr_strategy = request
         .read_from
+        .as_ref()
         .map(|rfr| {
             format!(
                 "\nRead from Replica mode: {}",
                 match rfr {
-                    ReadFrom::Primary => "Only primary",
-                    ReadFrom::PreferReplica => "Prefer replica",
+                    ReadFrom::Primary => "Only primary".to_string(),
+                    ReadFrom::PreferReplica => "Prefer replica".to_string(),
+                    ReadFrom::AZAffinity(az) => format!("AZ affinity ({az})"),
                 }
             )
         })
         .unwrap_or_default();
+    let replica_selection = request
+        .replica_selection
+        .as_ref()
+        .map(|replica_selection| format!("\nReplica selection: {replica_selection:?}"))
+        .unwrap_or_default();
     let connection_retry_strategy = request.connection_retry_strategy.as_ref().map(|strategy|
             format!("\nreconnect backoff strategy: number of increasing duration retries: {}, base: {}, factor: {}",
         strategy.number_of_retries, strategy.exponent_base, strategy.factor)).unwrap_or_default();
@@ -535,29 +845,116 @@ fn sanitized_request_string(request: &ConnectionRequest) -> String {
         .map(|pubsub_subscriptions| format!("\nPubsub subscriptions: {pubsub_subscriptions:?}"))
         .unwrap_or_default();
 
+    let big_key_detection = request
+        .big_key_detection
+        .map(|big_key_detection| {
+            format!(
+                "\nBig key detection: threshold {} bytes, report size {}",
+                big_key_detection.threshold, big_key_detection.max_keys
+            )
+        })
+        .unwrap_or_default();
+
+    let hot_key_detection = request
+        .hot_key_detection
+        .map(|hot_key_detection| {
+            format!(
+                "\nHot key detection: report size {}",
+                hot_key_detection.top_keys
+            )
+        })
+        .unwrap_or_default();
+
+    let command_concurrency_limits = if request.command_concurrency_limits.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\nCommand concurrency limits: {:?}",
+            request.command_concurrency_limits
+        )
+    };
+
+    let replica_lag_check = request
+        .replica_lag_check
+        .as_ref()
+        .map(|replica_lag_check| {
+            format!(
+                "\nReplica lag check: interval {:?}, max lag: {}",
+                replica_lag_check.interval,
+                replica_lag_check
+                    .max_lag
+                    .map(|max_lag| format!("{max_lag} bytes"))
+                    .unwrap_or_else(|| "unlimited".to_string())
+            )
+        })
+        .unwrap_or_default();
+
     format!(
-        "\nAddresses: {addresses}{tls_mode}{cluster_mode}{request_timeout}{rfr_strategy}{connection_retry_strategy}{database_id}{protocol}{client_name}{periodic_checks}{pubsub_subscriptions}",
+        "\nAddresses: {addresses}{configuration_profile}{tls_mode}{cluster_mode}{request_timeout}{blocking_command_timeout_extension}{rfr_strategy}{replica_selection}{connection_retry_strategy}{database_id}{protocol}{client_name}{periodic_checks}{pubsub_subscriptions}{subscriber_only}{replica_lag_check}{big_key_detection}{hot_key_detection}{payload_size_histograms}{sentinel_master_name}{connection_pool}{fail_fast_on_unavailable_slots}{command_concurrency_limits}",
     )
 }
 
 impl Client {
     pub async fn new(
-        request: ConnectionRequest,
+        mut request: ConnectionRequest,
         push_sender: Option<mpsc::UnboundedSender<PushInfo>>,
     ) -> Result<Self, ConnectionError> {
         const DEFAULT_CLIENT_CREATION_TIMEOUT: Duration = Duration::from_secs(10);
 
+        profiles::apply_configuration_profile(&mut request);
         log_info(
             "Connection configuration",
             sanitized_request_string(&request),
         );
+        let configuration_errors = validate_request(&request);
+        if !configuration_errors.is_empty() {
+            return Err(ConnectionError::InvalidConfiguration(configuration_errors));
+        }
         let request_timeout = to_duration(request.request_timeout, DEFAULT_RESPONSE_TIMEOUT);
+        let blocking_timeout_extension = request
+            .blocking_command_timeout_extension
+            .map(|extension| extension.as_secs_f64())
+            .unwrap_or(BLOCKING_CMD_TIMEOUT_EXTENSION);
+        let big_keys = request
+            .big_key_detection
+            .map(|big_key_detection| Arc::new(BigKeyTracker::new(big_key_detection)));
+        let hot_keys = request
+            .hot_key_detection
+            .map(|hot_key_detection| Arc::new(HotKeySketch::new(hot_key_detection)));
+        let payload_sizes = request
+            .payload_size_histograms
+            .then(|| Arc::new(PayloadSizeHistograms::default()));
+        let concurrency_limiter =
+            Arc::new(ConcurrencyLimiter::new(&request.command_concurrency_limits));
+        let slot_availability = request
+            .fail_fast_on_unavailable_slots
+            .then(|| Arc::new(SlotAvailability::default()));
+        let subscriptions = request
+            .pubsub_subscriptions
+            .as_ref()
+            .filter(|_| push_sender.is_some())
+            .map(|subscriptions| Arc::new(SubscriptionTracker::new(subscriptions)));
+        let push_sender = match (&subscriptions, push_sender) {
+            (Some(subscriptions), Some(push_sender)) => Some(subscriptions.intercept(push_sender)),
+            (_, push_sender) => push_sender,
+        };
         tokio::time::timeout(DEFAULT_CLIENT_CREATION_TIMEOUT, async move {
             let internal_client = if request.cluster_mode_enabled {
                 let client = create_cluster_client(request, push_sender)
                     .await
                     .map_err(ConnectionError::Cluster)?;
                 ClientWrapper::Cluster { client }
+            } else if let Some(master_name) = request.sentinel_master_name.clone() {
+                let tls_mode = request.tls_mode.unwrap_or_default();
+                let sentinels = std::mem::take(&mut request.addresses);
+                request.addresses = sentinel::discover_nodes(&sentinels, tls_mode, &master_name)
+                    .await
+                    .map_err(ConnectionError::Standalone)?;
+                let client = StandaloneClient::create_client(request, push_sender)
+                    .await
+                    .map_err(ConnectionError::Standalone)?;
+                client.start_sentinel_failover_watcher(sentinels, tls_mode, master_name);
+                ClientWrapper::Standalone(client)
             } else {
                 ClientWrapper::Standalone(
                     StandaloneClient::create_client(request, push_sender)
@@ -566,10 +963,28 @@ impl Client {
                 )
             };
 
-            Ok(Self {
+            let mut client = Self {
                 internal_client,
-                request_timeout,
-            })
+                request_timeout: Arc::new(AtomicU64::new(request_timeout.as_millis() as u64)),
+                blocking_timeout_extension,
+                big_keys,
+                hot_keys,
+                payload_sizes,
+                concurrency_limiter,
+                subscriptions,
+                slot_availability,
+                scheduler: Arc::new(CommandScheduler::default()),
+                server_capabilities: Arc::new(RwLock::new(None)),
+            };
+            if let ClientWrapper::Cluster { .. } = client.internal_client {
+                if let Err(err) = client.record_server_capabilities().await {
+                    log_warn(
+                        "server capabilities",
+                        format!("Failed to query the server's capabilities: {err}"),
+                    );
+                }
+            }
+            Ok(client)
         })
         .await
         .map_err(|_| ConnectionError::Timeout)
@@ -615,13 +1030,21 @@ mod tests {
         get_request_timeout, RequestTimeoutOption, TimeUnit, BLOCKING_CMD_TIMEOUT_EXTENSION,
     };
 
-    use super::get_timeout_from_cmd_arg;
+    use super::{get_timeout_from_cmd_arg, validate_request};
+    use crate::client::types::{
+        ConnectionRequest, NodeAddress, ReadFrom, ReplicaLagCheck, ReplicaSelection,
+    };
 
     #[test]
     fn test_get_timeout_from_cmd_returns_correct_duration_int() {
         let mut cmd = Cmd::new();
         cmd.arg("BLPOP").arg("key1").arg("key2").arg("5");
-        let result = get_timeout_from_cmd_arg(&cmd, cmd.args_iter().len() - 1, TimeUnit::Seconds);
+        let result = get_timeout_from_cmd_arg(
+            &cmd,
+            cmd.args_iter().len() - 1,
+            TimeUnit::Seconds,
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
@@ -635,7 +1058,12 @@ mod tests {
     fn test_get_timeout_from_cmd_returns_correct_duration_float() {
         let mut cmd = Cmd::new();
         cmd.arg("BLPOP").arg("key1").arg("key2").arg(0.5);
-        let result = get_timeout_from_cmd_arg(&cmd, cmd.args_iter().len() - 1, TimeUnit::Seconds);
+        let result = get_timeout_from_cmd_arg(
+            &cmd,
+            cmd.args_iter().len() - 1,
+            TimeUnit::Seconds,
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
@@ -649,7 +1077,12 @@ mod tests {
     fn test_get_timeout_from_cmd_returns_correct_duration_milliseconds() {
         let mut cmd = Cmd::new();
         cmd.arg("XREAD").arg("BLOCK").arg("500").arg("key");
-        let result = get_timeout_from_cmd_arg(&cmd, 2, TimeUnit::Milliseconds);
+        let result = get_timeout_from_cmd_arg(
+            &cmd,
+            2,
+            TimeUnit::Milliseconds,
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
@@ -663,7 +1096,12 @@ mod tests {
     fn test_get_timeout_from_cmd_returns_err_when_timeout_isnt_passed() {
         let mut cmd = Cmd::new();
         cmd.arg("BLPOP").arg("key1").arg("key2").arg("key3");
-        let result = get_timeout_from_cmd_arg(&cmd, cmd.args_iter().len() - 1, TimeUnit::Seconds);
+        let result = get_timeout_from_cmd_arg(
+            &cmd,
+            cmd.args_iter().len() - 1,
+            TimeUnit::Seconds,
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_err());
         let err = result.unwrap_err();
         println!("{:?}", err);
@@ -677,7 +1115,12 @@ mod tests {
             .arg("key1")
             .arg("key2")
             .arg(u32::MAX as u64 + 1);
-        let result = get_timeout_from_cmd_arg(&cmd, cmd.args_iter().len() - 1, TimeUnit::Seconds);
+        let result = get_timeout_from_cmd_arg(
+            &cmd,
+            cmd.args_iter().len() - 1,
+            TimeUnit::Seconds,
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_err());
         let err = result.unwrap_err();
         println!("{:?}", err);
@@ -688,7 +1131,12 @@ mod tests {
     fn test_get_timeout_from_cmd_returns_err_when_timeout_is_negative() {
         let mut cmd = Cmd::new();
         cmd.arg("BLPOP").arg("key1").arg("key2").arg(-1);
-        let result = get_timeout_from_cmd_arg(&cmd, cmd.args_iter().len() - 1, TimeUnit::Seconds);
+        let result = get_timeout_from_cmd_arg(
+            &cmd,
+            cmd.args_iter().len() - 1,
+            TimeUnit::Seconds,
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(err.to_string().to_lowercase().contains("negative"), "{err}");
@@ -698,7 +1146,12 @@ mod tests {
     fn test_get_timeout_from_cmd_returns_no_timeout_when_zero_is_passed() {
         let mut cmd = Cmd::new();
         cmd.arg("BLPOP").arg("key1").arg("key2").arg(0);
-        let result = get_timeout_from_cmd_arg(&cmd, cmd.args_iter().len() - 1, TimeUnit::Seconds);
+        let result = get_timeout_from_cmd_arg(
+            &cmd,
+            cmd.args_iter().len() - 1,
+            TimeUnit::Seconds,
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), RequestTimeoutOption::NoTimeout,);
     }
@@ -707,7 +1160,11 @@ mod tests {
     fn test_get_request_timeout_with_blocking_command_returns_cmd_arg_timeout() {
         let mut cmd = Cmd::new();
         cmd.arg("BLPOP").arg("key1").arg("key2").arg("500");
-        let result = get_request_timeout(&cmd, Duration::from_millis(100));
+        let result = get_request_timeout(
+            &cmd,
+            Duration::from_millis(100),
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
@@ -718,7 +1175,11 @@ mod tests {
 
         let mut cmd = Cmd::new();
         cmd.arg("XREADGROUP").arg("BLOCK").arg("500").arg("key");
-        let result = get_request_timeout(&cmd, Duration::from_millis(100));
+        let result = get_request_timeout(
+            &cmd,
+            Duration::from_millis(100),
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
@@ -729,7 +1190,11 @@ mod tests {
 
         let mut cmd = Cmd::new();
         cmd.arg("BLMPOP").arg("0.857").arg("key");
-        let result = get_request_timeout(&cmd, Duration::from_millis(100));
+        let result = get_request_timeout(
+            &cmd,
+            Duration::from_millis(100),
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
@@ -740,7 +1205,11 @@ mod tests {
 
         let mut cmd = Cmd::new();
         cmd.arg("WAIT").arg(1).arg("500");
-        let result = get_request_timeout(&cmd, Duration::from_millis(500));
+        let result = get_request_timeout(
+            &cmd,
+            Duration::from_millis(500),
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
@@ -754,14 +1223,170 @@ mod tests {
     fn test_get_request_timeout_non_blocking_command_returns_default_timeout() {
         let mut cmd = Cmd::new();
         cmd.arg("SET").arg("key").arg("value").arg("PX").arg("500");
-        let result = get_request_timeout(&cmd, Duration::from_millis(100));
+        let result = get_request_timeout(
+            &cmd,
+            Duration::from_millis(100),
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), Some(Duration::from_millis(100)));
 
         let mut cmd = Cmd::new();
         cmd.arg("XREADGROUP").arg("key");
-        let result = get_request_timeout(&cmd, Duration::from_millis(100));
+        let result = get_request_timeout(
+            &cmd,
+            Duration::from_millis(100),
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), Some(Duration::from_millis(100)));
     }
+
+    #[test]
+    fn test_get_request_timeout_with_configured_extension() {
+        let mut cmd = Cmd::new();
+        cmd.arg("BLPOP").arg("key").arg("30");
+        let result = get_request_timeout(&cmd, Duration::from_millis(100), 2.0);
+        assert_eq!(result.unwrap(), Some(Duration::from_secs(32)));
+    }
+
+    #[test]
+    fn test_get_request_timeout_for_waitaof() {
+        let mut cmd = Cmd::new();
+        cmd.arg("WAITAOF").arg("1").arg("0").arg("1000");
+        let result = get_request_timeout(
+            &cmd,
+            Duration::from_millis(100),
+            BLOCKING_CMD_TIMEOUT_EXTENSION,
+        );
+        assert_eq!(
+            result.unwrap(),
+            Some(Duration::from_secs_f64(
+                1.0 + BLOCKING_CMD_TIMEOUT_EXTENSION
+            ))
+        );
+    }
+
+    fn request_with_addresses(count: u16) -> ConnectionRequest {
+        ConnectionRequest {
+            addresses: (0..count)
+                .map(|index| NodeAddress {
+                    host: "localhost".to_string(),
+                    port: 6379 + index,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_request_accepts_valid_request() {
+        let request = ConnectionRequest {
+            read_from: Some(ReadFrom::PreferReplica),
+            replica_selection: Some(ReplicaSelection::Weighted(vec![1, 2])),
+            ..request_with_addresses(2)
+        };
+        assert!(validate_request(&request).is_empty());
+    }
+
+    #[test]
+    fn test_validate_request_accepts_address_without_port() {
+        let request = ConnectionRequest {
+            addresses: vec![NodeAddress {
+                host: "localhost".to_string(),
+                port: 0,
+            }],
+            ..Default::default()
+        };
+        assert!(validate_request(&request).is_empty());
+    }
+
+    #[test]
+    fn test_validate_request_rejects_empty_host() {
+        let request = ConnectionRequest {
+            addresses: vec![NodeAddress {
+                host: String::new(),
+                port: 6379,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(validate_request(&request), vec!["invalid address `:6379`"]);
+    }
+
+    #[test]
+    fn test_validate_request_reports_all_errors() {
+        let request = ConnectionRequest {
+            replica_selection: Some(ReplicaSelection::Weighted(vec![1])),
+            replica_lag_check: Some(ReplicaLagCheck {
+                interval: Duration::ZERO,
+                max_lag: None,
+            }),
+            subscriber_only: true,
+            ..request_with_addresses(2)
+        };
+        assert_eq!(
+            validate_request(&request),
+            vec![
+                "a replica selection strategy was set without reading from replicas",
+                "weighted replica selection has 1 weights for 2 addresses",
+                "the replica lag check interval must be positive",
+                "subscriber only mode was set without pubsub subscriptions",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_request_sentinel_options() {
+        let request = ConnectionRequest {
+            cluster_mode_enabled: true,
+            sentinel_master_name: Some("mymaster".to_string()),
+            read_from: Some(ReadFrom::PreferReplica),
+            replica_selection: Some(ReplicaSelection::Weighted(vec![1])),
+            ..request_with_addresses(2)
+        };
+        assert_eq!(
+            validate_request(&request),
+            vec![
+                "sentinel is supported only in standalone mode",
+                "weighted replica selection isn't supported with sentinel",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_request_connection_pools_in_cluster_mode() {
+        let request = ConnectionRequest {
+            cluster_mode_enabled: true,
+            connections_per_node: 4,
+            ..request_with_addresses(1)
+        };
+        assert_eq!(
+            validate_request(&request),
+            vec!["multiple connections per node are supported only in standalone mode"]
+        );
+    }
+
+    #[test]
+    fn test_validate_request_az_affinity() {
+        let request = ConnectionRequest {
+            cluster_mode_enabled: true,
+            read_from: Some(ReadFrom::AZAffinity(String::new())),
+            ..request_with_addresses(1)
+        };
+        assert_eq!(
+            validate_request(&request),
+            vec![
+                "AZ affinity is supported only in standalone mode",
+                "AZ affinity requires the client's availability zone",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_request_requires_addresses() {
+        assert_eq!(
+            validate_request(&ConnectionRequest::default()),
+            vec!["no addresses were provided"]
+        );
+    }
 }
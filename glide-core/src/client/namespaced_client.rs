@@ -0,0 +1,130 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::command_keys::command_keys;
+use super::{Client, ConnectionError, ConnectionRequest};
+use redis::cluster_routing::RoutingInfo;
+use redis::{Cmd, ErrorKind, PushInfo, RedisError, RedisResult, Value};
+use tokio::sync::mpsc;
+
+/// A client connected to several clusters, which routes each command to a cluster by its key's prefix.
+/// This allows sharding data across clusters above the slot layer.
+#[derive(Clone)]
+pub struct NamespacedClient {
+    /// The clients of the namespaces, sorted from the longest prefix to the shortest.
+    namespaces: Vec<(Vec<u8>, Client)>,
+    /// Receives commands without keys, or whose keys don't match any namespace.
+    default_client: Client,
+}
+
+impl NamespacedClient {
+    /// Connects to all clusters. `namespaces` maps each key prefix to the connection request of its cluster.
+    pub async fn new(
+        namespaces: Vec<(String, ConnectionRequest)>,
+        default_request: ConnectionRequest,
+        push_sender: Option<mpsc::UnboundedSender<PushInfo>>,
+    ) -> Result<Self, ConnectionError> {
+        let default_client = Client::new(default_request, push_sender.clone()).await?;
+        let mut clients = Vec::with_capacity(namespaces.len());
+        for (prefix, request) in namespaces {
+            let client = Client::new(request, push_sender.clone()).await?;
+            clients.push((prefix.into_bytes(), client));
+        }
+        clients.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        Ok(Self {
+            namespaces: clients,
+            default_client,
+        })
+    }
+
+    /// Returns the client of the namespace with the longest prefix matching the command's keys.
+    fn client_for_cmd(&mut self, cmd: &Cmd) -> RedisResult<&mut Client> {
+        let index = keys_namespace_index(&self.namespaces, &command_keys(cmd))?;
+        Ok(match index {
+            Some(index) => &mut self.namespaces[index].1,
+            None => &mut self.default_client,
+        })
+    }
+
+    /// Sends the command to the cluster of its keys' namespace. The routing applies within that cluster. Commands
+    /// whose keys are in different namespaces are rejected.
+    pub async fn send_command(
+        &mut self,
+        cmd: &Cmd,
+        routing: Option<RoutingInfo>,
+    ) -> RedisResult<Value> {
+        self.client_for_cmd(cmd)?.send_command(cmd, routing).await
+    }
+}
+
+fn namespace_index<T>(namespaces: &[(Vec<u8>, T)], key: &[u8]) -> Option<usize> {
+    namespaces
+        .iter()
+        .position(|(prefix, _)| key.starts_with(prefix))
+}
+
+/// Returns the namespace of the keys, or None if they don't match any namespace. Keys of different namespaces are
+/// stored in different clusters, so a command can't access them together.
+fn keys_namespace_index<T>(
+    namespaces: &[(Vec<u8>, T)],
+    keys: &[&[u8]],
+) -> RedisResult<Option<usize>> {
+    let mut indices = keys.iter().map(|key| namespace_index(namespaces, key));
+    let Some(index) = indices.next() else {
+        return Ok(None);
+    };
+    if indices.any(|other_index| other_index != index) {
+        return Err(RedisError::from((
+            ErrorKind::ClientError,
+            "Keys of different namespaces",
+            "all of the command's keys must be in the same namespace".to_string(),
+        )));
+    }
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keys_namespace_index, namespace_index};
+    use redis::ErrorKind;
+
+    #[test]
+    fn test_longest_matching_prefix_is_chosen() {
+        // Namespaces are kept sorted from the longest prefix to the shortest.
+        let namespaces = vec![
+            (b"user:eu:".to_vec(), ()),
+            (b"user:".to_vec(), ()),
+            (b"order:".to_vec(), ()),
+        ];
+        assert_eq!(namespace_index(&namespaces, b"user:eu:1"), Some(0));
+        assert_eq!(namespace_index(&namespaces, b"user:us:1"), Some(1));
+        assert_eq!(namespace_index(&namespaces, b"order:1"), Some(2));
+        assert_eq!(namespace_index(&namespaces, b"session:1"), None);
+    }
+
+    #[test]
+    fn test_keys_of_different_namespaces_are_rejected() {
+        let namespaces = vec![(b"user:".to_vec(), ()), (b"order:".to_vec(), ())];
+        assert_eq!(
+            keys_namespace_index(&namespaces, &[b"user:1", b"user:2"]).unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            keys_namespace_index(&namespaces, &[b"session:1", b"session:2"]).unwrap(),
+            None
+        );
+        assert_eq!(keys_namespace_index(&namespaces, &[]).unwrap(), None);
+        assert_eq!(
+            keys_namespace_index(&namespaces, &[b"user:1", b"order:1"])
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ClientError
+        );
+        assert_eq!(
+            keys_namespace_index(&namespaces, &[b"user:1", b"session:1"])
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ClientError
+        );
+    }
+}
@@ -0,0 +1,145 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::{Client, ClientWrapper};
+use rand::seq::SliceRandom;
+use redis::cluster_routing::{
+    MultipleNodeRoutingInfo, Routable, RoutingInfo, SingleNodeRoutingInfo,
+};
+use redis::{Cmd, ErrorKind, RedisError, RedisResult, Value};
+
+/// The nodes that a command can be pinned to.
+#[derive(Debug, PartialEq)]
+enum PinnableNodes {
+    /// The command is already pinned to the node.
+    Address {
+        host: String,
+        port: u16,
+    },
+    Primaries,
+    AllNodes,
+}
+
+/// Returns the nodes that a command with the routing can be pinned to. Commands that are routed to several nodes, such
+/// as `INFO` or `CLIENT LIST`, are sent only to the node they're pinned to. Commands that are routed by their keys
+/// can't be pinned, since they must reach the nodes that own their slots.
+fn pinnable_nodes(routing: Option<RoutingInfo>) -> Option<PinnableNodes> {
+    match routing {
+        Some(RoutingInfo::SingleNode(SingleNodeRoutingInfo::ByAddress { host, port })) => {
+            Some(PinnableNodes::Address { host, port })
+        }
+        None
+        | Some(RoutingInfo::SingleNode(SingleNodeRoutingInfo::Random))
+        | Some(RoutingInfo::MultiNode((MultipleNodeRoutingInfo::AllMasters, _))) => {
+            Some(PinnableNodes::Primaries)
+        }
+        Some(RoutingInfo::MultiNode((MultipleNodeRoutingInfo::AllNodes, _))) => {
+            Some(PinnableNodes::AllNodes)
+        }
+        Some(_) => None,
+    }
+}
+
+impl Client {
+    /// Sends a command that isn't routed by its keys to a randomly chosen node, and returns an array of the node's
+    /// address and the command's response. Commands that are routed to several nodes are sent only to the chosen
+    /// node. Routing subsequent commands to the returned address makes sure they reach the same node, which is needed
+    /// for sequences of per-node commands, such as `INFO` followed by `DEBUG`.
+    pub async fn send_command_pinned(
+        &mut self,
+        cmd: &Cmd,
+        routing: Option<RoutingInfo>,
+    ) -> RedisResult<Value> {
+        if matches!(self.internal_client, ClientWrapper::Standalone(_)) {
+            return Err(RedisError::from((
+                ErrorKind::ClientError,
+                "Pinning commands to nodes is supported only in cluster mode",
+            )));
+        }
+        let primaries_only =
+            match pinnable_nodes(routing.or_else(|| RoutingInfo::for_routable(cmd))) {
+                Some(PinnableNodes::Address { host, port }) => {
+                    return self.send_command_to_pinned_node(cmd, host, port).await;
+                }
+                Some(PinnableNodes::Primaries) => true,
+                Some(PinnableNodes::AllNodes) => false,
+                None => {
+                    return Err(RedisError::from((
+                        ErrorKind::ClientError,
+                        "Commands that are routed by their keys can't be pinned",
+                        format!("{:?}", cmd.command().map(String::from_utf8_lossy)),
+                    )))
+                }
+            };
+        let (host, port) = self
+            .cluster_node_addresses(primaries_only)
+            .await?
+            .choose(&mut rand::thread_rng())
+            .cloned()
+            .ok_or_else(|| RedisError::from((ErrorKind::ClientError, "No reachable node found")))?;
+        self.send_command_to_pinned_node(cmd, host, port).await
+    }
+
+    async fn send_command_to_pinned_node(
+        &mut self,
+        cmd: &Cmd,
+        host: String,
+        port: u16,
+    ) -> RedisResult<Value> {
+        let address = format!("{host}:{port}");
+        let routing = RoutingInfo::SingleNode(SingleNodeRoutingInfo::ByAddress { host, port });
+        let value = self.send_command(cmd, Some(routing)).await?;
+        Ok(Value::Array(vec![
+            Value::BulkString(address.into_bytes()),
+            value,
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pinnable_nodes, PinnableNodes};
+    use redis::cluster_routing::{MultipleNodeRoutingInfo, RoutingInfo, SingleNodeRoutingInfo};
+    use redis::cmd;
+
+    #[test]
+    fn test_multi_node_commands_are_pinned_to_a_single_node() {
+        assert_eq!(
+            pinnable_nodes(Some(RoutingInfo::MultiNode((
+                MultipleNodeRoutingInfo::AllNodes,
+                None
+            )))),
+            Some(PinnableNodes::AllNodes)
+        );
+        assert_eq!(
+            pinnable_nodes(Some(RoutingInfo::MultiNode((
+                MultipleNodeRoutingInfo::AllMasters,
+                None
+            )))),
+            Some(PinnableNodes::Primaries)
+        );
+        assert_eq!(
+            pinnable_nodes(Some(RoutingInfo::SingleNode(SingleNodeRoutingInfo::Random))),
+            Some(PinnableNodes::Primaries)
+        );
+        assert_eq!(
+            pinnable_nodes(Some(RoutingInfo::SingleNode(
+                SingleNodeRoutingInfo::ByAddress {
+                    host: "host".to_string(),
+                    port: 6379
+                }
+            ))),
+            Some(PinnableNodes::Address {
+                host: "host".to_string(),
+                port: 6379
+            })
+        );
+    }
+
+    #[test]
+    fn test_keyed_commands_cant_be_pinned() {
+        let mut get = cmd("GET");
+        get.arg("foo");
+        assert_eq!(pinnable_nodes(RoutingInfo::for_routable(&get)), None);
+    }
+}
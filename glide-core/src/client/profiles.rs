@@ -0,0 +1,139 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::{ConfigurationProfile, ConnectionRequest, ConnectionRetryStrategy, TlsMode};
+use std::time::Duration;
+
+/// The options preset by a configuration profile.
+struct ProfilePresets {
+    request_timeout_ms: u32,
+    retry_strategy: ConnectionRetryStrategy,
+    blocking_command_timeout_extension: Option<Duration>,
+    tls_mode: Option<TlsMode>,
+    cluster_mode_enabled: bool,
+}
+
+fn presets(profile: ConfigurationProfile) -> ProfilePresets {
+    match profile {
+        ConfigurationProfile::AwsElastiCacheClusterTls => ProfilePresets {
+            // TLS handshakes and cross-AZ hops make the default timeout too tight.
+            request_timeout_ms: 2000,
+            // Node replacements during maintenance can take several seconds.
+            retry_strategy: ConnectionRetryStrategy {
+                exponent_base: 2,
+                factor: 100,
+                number_of_retries: 8,
+            },
+            blocking_command_timeout_extension: None,
+            tls_mode: Some(TlsMode::SecureTls),
+            cluster_mode_enabled: true,
+        },
+        ConfigurationProfile::LowLatencyLan => ProfilePresets {
+            request_timeout_ms: 50,
+            retry_strategy: ConnectionRetryStrategy {
+                exponent_base: 2,
+                factor: 10,
+                number_of_retries: 3,
+            },
+            blocking_command_timeout_extension: Some(Duration::from_millis(100)),
+            tls_mode: None,
+            cluster_mode_enabled: false,
+        },
+        ConfigurationProfile::HighThroughputBatch => ProfilePresets {
+            request_timeout_ms: 10_000,
+            retry_strategy: ConnectionRetryStrategy {
+                exponent_base: 2,
+                factor: 500,
+                number_of_retries: 5,
+            },
+            blocking_command_timeout_extension: None,
+            tls_mode: None,
+            cluster_mode_enabled: false,
+        },
+    }
+}
+
+/// Fills the options that aren't set in the request with the presets of its configuration profile.
+/// Cluster mode can't be unset, so a profile of a cluster deployment always enables it.
+pub(super) fn apply_configuration_profile(request: &mut ConnectionRequest) {
+    let Some(profile) = request.configuration_profile else {
+        return;
+    };
+    let presets = presets(profile);
+    request
+        .request_timeout
+        .get_or_insert(presets.request_timeout_ms);
+    request
+        .connection_retry_strategy
+        .get_or_insert(presets.retry_strategy);
+    if request.blocking_command_timeout_extension.is_none() {
+        request.blocking_command_timeout_extension = presets.blocking_command_timeout_extension;
+    }
+    if request.tls_mode.is_none() {
+        request.tls_mode = presets.tls_mode;
+    }
+    request.cluster_mode_enabled |= presets.cluster_mode_enabled;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply_configuration_profile;
+    use crate::client::{ConfigurationProfile, ConnectionRequest, TlsMode};
+    use crate::connection_request as protobuf;
+
+    #[test]
+    fn test_profile_fills_unset_options() {
+        let mut request = ConnectionRequest {
+            configuration_profile: Some(ConfigurationProfile::AwsElastiCacheClusterTls),
+            ..Default::default()
+        };
+        apply_configuration_profile(&mut request);
+        assert_eq!(request.request_timeout, Some(2000));
+        assert_eq!(
+            request
+                .connection_retry_strategy
+                .map(|strategy| strategy.number_of_retries),
+            Some(8)
+        );
+        assert!(request.tls_mode == Some(TlsMode::SecureTls));
+        assert!(request.cluster_mode_enabled);
+    }
+
+    #[test]
+    fn test_request_options_override_profile() {
+        let mut request = ConnectionRequest {
+            configuration_profile: Some(ConfigurationProfile::AwsElastiCacheClusterTls),
+            request_timeout: Some(500),
+            tls_mode: Some(TlsMode::InsecureTls),
+            ..Default::default()
+        };
+        apply_configuration_profile(&mut request);
+        assert_eq!(request.request_timeout, Some(500));
+        assert!(request.tls_mode == Some(TlsMode::InsecureTls));
+    }
+
+    #[test]
+    fn test_no_profile_keeps_request() {
+        let mut request = ConnectionRequest::default();
+        apply_configuration_profile(&mut request);
+        assert_eq!(request.request_timeout, None);
+        assert!(request.connection_retry_strategy.is_none());
+        assert!(request.tls_mode.is_none());
+    }
+
+    #[test]
+    fn test_profile_treats_no_tls_as_unset() {
+        let mut protobuf_request = protobuf::ConnectionRequest::new();
+        protobuf_request.tls_mode = protobuf::TlsMode::NoTls.into();
+        let request = ConnectionRequest::from(protobuf_request.clone());
+        assert!(request.tls_mode == Some(TlsMode::NoTls));
+
+        // NoTls is the protobuf default, so with a profile it's replaced by the profile's TLS mode.
+        protobuf_request.configuration_profile =
+            protobuf::ConfigurationProfile::AwsElastiCacheClusterTls.into();
+        let mut request = ConnectionRequest::from(protobuf_request);
+        assert!(request.tls_mode.is_none());
+        apply_configuration_profile(&mut request);
+        assert!(request.tls_mode == Some(TlsMode::SecureTls));
+    }
+}
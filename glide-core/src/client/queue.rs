@@ -0,0 +1,188 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+// A reliable queue, implemented over a stream and a consumer group.
+// Reserved messages stay in the group's pending entries list until they're acked, and are handed out again to
+// another consumer once their visibility timeout expires.
+use super::Client;
+use redis::{cmd, RedisResult, Value};
+use std::time::Duration;
+
+const CONSUMER_GROUP: &str = "glide-queue";
+const PAYLOAD_FIELD: &[u8] = b"payload";
+
+/// The stream to which dead-lettered messages are moved. It shares the queue's hash slot.
+fn dead_letter_key(queue: &[u8]) -> Vec<u8> {
+    [b"{".as_slice(), queue, b"}:dead-letter"].concat()
+}
+
+/// Extracts the first message from a map of stream entry IDs to their fields, as an array of its ID and payload.
+fn first_message(entries: Value) -> Option<Value> {
+    let Value::Map(entries) = entries else {
+        return None;
+    };
+    let (id, fields) = entries.into_iter().next()?;
+    let Value::Array(fields) = fields else {
+        return None;
+    };
+    let payload = fields.into_iter().find_map(|pair| match pair {
+        Value::Array(mut pair) if pair.len() == 2 => {
+            let value = pair.pop()?;
+            (pair.pop()? == Value::BulkString(PAYLOAD_FIELD.to_vec())).then_some(value)
+        }
+        _ => None,
+    })?;
+    Some(Value::Array(vec![id, payload]))
+}
+
+impl Client {
+    /// Adds a message to the end of the queue, and returns its ID.
+    pub async fn queue_enqueue(&mut self, queue: &[u8], payload: &[u8]) -> RedisResult<Value> {
+        let mut xadd = cmd("XADD");
+        xadd.arg(queue).arg("*").arg(PAYLOAD_FIELD).arg(payload);
+        self.send_command(&xadd, None).await
+    }
+
+    /// Reserves a message for the consumer, and returns its ID and payload, or nil if the queue is empty.
+    /// Messages that were reserved for longer than the visibility timeout without being acked are reserved again
+    /// before new messages.
+    pub async fn queue_reserve(
+        &mut self,
+        queue: &[u8],
+        consumer: &str,
+        visibility_timeout: Duration,
+    ) -> RedisResult<Value> {
+        let mut xautoclaim = cmd("XAUTOCLAIM");
+        xautoclaim
+            .arg(queue)
+            .arg(CONSUMER_GROUP)
+            .arg(consumer)
+            .arg(visibility_timeout.as_millis() as u64)
+            .arg("0-0")
+            .arg("COUNT")
+            .arg(1);
+        let expired = match self.send_command(&xautoclaim, None).await {
+            Err(err) if err.code() == Some("NOGROUP") => {
+                self.create_consumer_group(queue).await?;
+                Value::Nil
+            }
+            result => result?,
+        };
+        if let Value::Array(expired) = expired {
+            if let Some(message) = expired.into_iter().nth(1).and_then(first_message) {
+                return Ok(message);
+            }
+        }
+
+        let mut xreadgroup = cmd("XREADGROUP");
+        xreadgroup
+            .arg("GROUP")
+            .arg(CONSUMER_GROUP)
+            .arg(consumer)
+            .arg("COUNT")
+            .arg(1)
+            .arg("STREAMS")
+            .arg(queue)
+            .arg(">");
+        let Value::Map(streams) = self.send_command(&xreadgroup, None).await? else {
+            return Ok(Value::Nil);
+        };
+        Ok(streams
+            .into_iter()
+            .next()
+            .and_then(|(_, entries)| first_message(entries))
+            .unwrap_or(Value::Nil))
+    }
+
+    /// Acknowledges that the message was processed and removes it from the queue.
+    /// Returns the number of acknowledged messages - 0 if the message was already acked.
+    pub async fn queue_ack(&mut self, queue: &[u8], id: &[u8]) -> RedisResult<Value> {
+        let mut xack = cmd("XACK");
+        xack.arg(queue).arg(CONSUMER_GROUP).arg(id);
+        let acked = self.send_command(&xack, None).await?;
+        let mut xdel = cmd("XDEL");
+        xdel.arg(queue).arg(id);
+        self.send_command(&xdel, None).await?;
+        Ok(acked)
+    }
+
+    /// Returns a reserved message to the end of the queue, or moves it to the queue's dead-letter stream.
+    /// Returns the number of moved messages - 0 if the message was already acked.
+    pub async fn queue_nack(
+        &mut self,
+        queue: &[u8],
+        id: &[u8],
+        dead_letter: bool,
+    ) -> RedisResult<Value> {
+        let mut xrange = cmd("XRANGE");
+        xrange.arg(queue).arg(id).arg(id);
+        let Some(Value::Array(mut message)) =
+            first_message(self.send_command(&xrange, None).await?)
+        else {
+            return Ok(Value::Int(0));
+        };
+        let Some(Value::BulkString(payload)) = message.pop() else {
+            return Ok(Value::Int(0));
+        };
+        let target = if dead_letter {
+            dead_letter_key(queue)
+        } else {
+            queue.to_vec()
+        };
+        let mut xadd = cmd("XADD");
+        xadd.arg(target).arg("*").arg(PAYLOAD_FIELD).arg(payload);
+        self.send_command(&xadd, None).await?;
+        self.queue_ack(queue, id).await
+    }
+
+    async fn create_consumer_group(&mut self, queue: &[u8]) -> RedisResult<()> {
+        let mut xgroup = cmd("XGROUP");
+        xgroup
+            .arg("CREATE")
+            .arg(queue)
+            .arg(CONSUMER_GROUP)
+            .arg(0)
+            .arg("MKSTREAM");
+        match self.send_command(&xgroup, None).await {
+            // The group might have been created concurrently by another client.
+            Err(err) if err.code() == Some("BUSYGROUP") => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dead_letter_key, first_message};
+    use redis::Value;
+
+    #[test]
+    fn test_dead_letter_key_shares_queue_slot() {
+        assert_eq!(dead_letter_key(b"jobs"), b"{jobs}:dead-letter".to_vec());
+    }
+
+    #[test]
+    fn test_first_message_extracts_payload() {
+        let entries = Value::Map(vec![(
+            Value::BulkString(b"1-0".to_vec()),
+            Value::Array(vec![Value::Array(vec![
+                Value::BulkString(b"payload".to_vec()),
+                Value::BulkString(b"job".to_vec()),
+            ])]),
+        )]);
+        assert_eq!(
+            first_message(entries),
+            Some(Value::Array(vec![
+                Value::BulkString(b"1-0".to_vec()),
+                Value::BulkString(b"job".to_vec()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_first_message_skips_deleted_entries() {
+        let entries = Value::Map(vec![(Value::BulkString(b"1-0".to_vec()), Value::Nil)]);
+        assert_eq!(first_message(entries), None);
+        assert_eq!(first_message(Value::Nil), None);
+    }
+}
@@ -1,22 +1,35 @@
 /**
  * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
  */
-use super::{NodeAddress, TlsMode};
+use super::capabilities::query_server_capabilities;
+use super::{NodeAddress, ServerCapabilities, TlsMode};
 use crate::retry_strategies::RetryStrategy;
 use futures_intrusive::sync::ManualResetEvent;
 use logger_core::{log_debug, log_trace, log_warn};
 use redis::aio::MultiplexedConnection;
-use redis::{PushInfo, RedisConnectionInfo, RedisError, RedisResult};
+use redis::{ErrorKind, PushInfo, PushKind, RedisConnectionInfo, RedisError, RedisResult};
 use std::fmt;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::sync::Mutex;
+use std::sync::{Mutex, RwLock};
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::task;
 use tokio_retry::Retry;
 
-use super::{run_with_timeout, DEFAULT_CONNECTION_ATTEMPT_TIMEOUT};
+use super::{run_with_timeout, DEFAULT_CONNECTION_ATTEMPT_TIMEOUT, RECONNECTION_PUSH_KIND};
+
+/// The weight of the latest sample in the moving averages of the connection's latency and error rate.
+const MOVING_AVERAGE_WEIGHT: f64 = 0.1;
+
+/// The minimal wait between reconnect attempts that failed on authentication. Retrying with the same credentials is
+/// unlikely to succeed soon, so these attempts back off more than attempts that failed on network errors.
+const AUTHENTICATION_FAILURE_BACKOFF: Duration = Duration::from_secs(10);
+
+fn is_authentication_error(err: &RedisError) -> bool {
+    err.kind() == ErrorKind::AuthenticationFailed
+        || matches!(err.code(), Some("NOAUTH") | Some("WRONGPASS"))
+}
 
 /// The object that is used in order to recreate a connection after a disconnect.
 struct ConnectionBackend {
@@ -41,6 +54,45 @@ enum ConnectionState {
 struct InnerReconnectingConnection {
     state: Mutex<ConnectionState>,
     backend: ConnectionBackend,
+    /// The number of requests sent on this connection that are still awaiting a response.
+    inflight_requests: AtomicUsize,
+    /// Exponential moving average of the requests' latency in microseconds, stored as `f64` bits.
+    latency_average_us: AtomicU64,
+    /// Exponential moving average of the rate of requests failing with connection errors, stored as `f64` bits.
+    error_rate_average: AtomicU64,
+    /// The number of health checks performed on the node, and how many of them failed.
+    health_checks: AtomicU64,
+    failed_health_checks: AtomicU64,
+    /// The number of reconnect attempts that failed on authentication.
+    authentication_failures: AtomicU64,
+    /// The number of successful reconnects.
+    reconnections: AtomicU64,
+    /// The server's capabilities, negotiated whenever the connection is established. None if they're unknown.
+    server_capabilities: RwLock<Option<ServerCapabilities>>,
+}
+
+fn update_moving_average(average: &AtomicU64, sample: f64, is_first_sample: bool) {
+    let _ = average.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+        let updated = if is_first_sample {
+            sample
+        } else {
+            f64::from_bits(bits) * (1.0 - MOVING_AVERAGE_WEIGHT) + sample * MOVING_AVERAGE_WEIGHT
+        };
+        Some(updated.to_bits())
+    });
+}
+
+/// Marks a request as inflight on a connection until the guard is dropped.
+pub(super) struct InflightRequestGuard {
+    connection: Arc<InnerReconnectingConnection>,
+}
+
+impl Drop for InflightRequestGuard {
+    fn drop(&mut self) {
+        self.connection
+            .inflight_requests
+            .fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 #[derive(Clone)]
@@ -75,7 +127,8 @@ async fn create_connection(
     let action = || get_multiplexed_connection(client, push_sender.clone());
 
     match Retry::spawn(retry_strategy.get_iterator(), action).await {
-        Ok(connection) => {
+        Ok(mut connection) => {
+            let server_capabilities = negotiate_server_capabilities(&mut connection).await;
             log_debug(
                 "connection creation",
                 format!(
@@ -90,6 +143,14 @@ async fn create_connection(
                 inner: Arc::new(InnerReconnectingConnection {
                     state: Mutex::new(ConnectionState::Connected(connection)),
                     backend: connection_backend,
+                    inflight_requests: AtomicUsize::new(0),
+                    latency_average_us: AtomicU64::new(0),
+                    error_rate_average: AtomicU64::new(0),
+                    health_checks: AtomicU64::new(0),
+                    failed_health_checks: AtomicU64::new(0),
+                    authentication_failures: AtomicU64::new(0),
+                    reconnections: AtomicU64::new(0),
+                    server_capabilities: RwLock::new(server_capabilities),
                 }),
                 push_sender,
             })
@@ -109,6 +170,14 @@ async fn create_connection(
                 inner: Arc::new(InnerReconnectingConnection {
                     state: Mutex::new(ConnectionState::InitializedDisconnected),
                     backend: connection_backend,
+                    inflight_requests: AtomicUsize::new(0),
+                    latency_average_us: AtomicU64::new(0),
+                    error_rate_average: AtomicU64::new(0),
+                    health_checks: AtomicU64::new(0),
+                    failed_health_checks: AtomicU64::new(0),
+                    authentication_failures: AtomicU64::new(0),
+                    reconnections: AtomicU64::new(0),
+                    server_capabilities: RwLock::new(None),
                 }),
                 push_sender,
             };
@@ -118,6 +187,25 @@ async fn create_connection(
     }
 }
 
+/// Negotiates the server's capabilities on a newly established connection. Servers older than 6.0 don't support
+/// `HELLO`, and their capabilities stay unknown.
+async fn negotiate_server_capabilities(
+    connection: &mut MultiplexedConnection,
+) -> Option<ServerCapabilities> {
+    run_with_timeout(
+        Some(DEFAULT_CONNECTION_ATTEMPT_TIMEOUT),
+        query_server_capabilities(connection),
+    )
+    .await
+    .map_err(|err| {
+        log_debug(
+            "server capabilities",
+            format!("Failed to query the server's capabilities: {err}"),
+        )
+    })
+    .ok()
+}
+
 fn get_client(
     address: &NodeAddress,
     tls_mode: TlsMode,
@@ -165,7 +253,7 @@ impl ReconnectingConnection {
         create_connection(backend, connection_retry_strategy, push_sender).await
     }
 
-    fn node_address(&self) -> String {
+    pub(super) fn node_address(&self) -> String {
         self.inner
             .backend
             .connection_info
@@ -221,6 +309,13 @@ impl ReconnectingConnection {
 
         let connection_clone = self.clone();
         let push_sender = self.push_sender.clone();
+        if let Some(push_sender) = &push_sender {
+            // The marker is sent before the new connection resubscribes, so it precedes all of its messages.
+            let _ = push_sender.send(PushInfo {
+                kind: PushKind::Other(RECONNECTION_PUSH_KIND.to_string()),
+                data: vec![],
+            });
+        }
         // The reconnect task is spawned instead of awaited here, so that the reconnect attempt will continue in the
         // background, regardless of whether the calling task is dropped or not.
         task::spawn(async move {
@@ -244,9 +339,17 @@ impl ReconnectingConnection {
                             tokio::time::sleep(sleep_duration).await;
                             continue;
                         }
+                        let server_capabilities =
+                            negotiate_server_capabilities(&mut connection).await;
+                        *connection_clone.inner.server_capabilities.write().unwrap() =
+                            server_capabilities;
                         {
                             let mut guard = connection_clone.inner.state.lock().unwrap();
                             log_debug("reconnect", "completed successfully");
+                            connection_clone
+                                .inner
+                                .reconnections
+                                .fetch_add(1, Ordering::Relaxed);
                             connection_clone
                                 .inner
                                 .backend
@@ -256,12 +359,108 @@ impl ReconnectingConnection {
                         }
                         return;
                     }
+                    Err(err) if is_authentication_error(&err) => {
+                        connection_clone
+                            .inner
+                            .authentication_failures
+                            .fetch_add(1, Ordering::Relaxed);
+                        log_warn(
+                            "reconnect",
+                            format!("authentication to {connection_clone:?} failed: {err}"),
+                        );
+                        tokio::time::sleep(sleep_duration.max(AUTHENTICATION_FAILURE_BACKOFF)).await
+                    }
                     Err(_) => tokio::time::sleep(sleep_duration).await,
                 }
             }
         });
     }
 
+    pub(super) fn track_inflight_request(&self) -> InflightRequestGuard {
+        self.inner.inflight_requests.fetch_add(1, Ordering::Relaxed);
+        InflightRequestGuard {
+            connection: self.inner.clone(),
+        }
+    }
+
+    pub(super) fn inflight_requests(&self) -> usize {
+        self.inner.inflight_requests.load(Ordering::Relaxed)
+    }
+
+    /// Updates the moving averages of the connection's latency and error rate with a completed request.
+    pub(super) fn record_request(&self, latency: Duration, is_connection_error: bool) {
+        let is_first_sample = self.inner.latency_average_us.load(Ordering::Relaxed) == 0;
+        update_moving_average(
+            &self.inner.latency_average_us,
+            latency.as_micros() as f64,
+            is_first_sample,
+        );
+        update_moving_average(
+            &self.inner.error_rate_average,
+            if is_connection_error { 1.0 } else { 0.0 },
+            is_first_sample,
+        );
+    }
+
+    pub(super) fn latency_average(&self) -> Duration {
+        Duration::from_secs_f64(
+            f64::from_bits(self.inner.latency_average_us.load(Ordering::Relaxed)) / 1_000_000.0,
+        )
+    }
+
+    pub(super) fn error_rate_average(&self) -> f64 {
+        f64::from_bits(self.inner.error_rate_average.load(Ordering::Relaxed))
+    }
+
+    /// Creates a connection to the node that is separate from the connection used for user requests, so that
+    /// health checks don't compete with user requests.
+    #[cfg(standalone_heartbeat)]
+    pub(super) async fn create_management_connection(&self) -> RedisResult<MultiplexedConnection> {
+        get_multiplexed_connection(&self.inner.backend.connection_info, None).await
+    }
+
+    #[cfg(standalone_heartbeat)]
+    pub(super) fn record_health_check(&self, succeeded: bool) {
+        self.inner.health_checks.fetch_add(1, Ordering::Relaxed);
+        if !succeeded {
+            self.inner
+                .failed_health_checks
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the number of health checks performed on the node, and how many of them failed.
+    pub(super) fn health_checks(&self) -> (u64, u64) {
+        (
+            self.inner.health_checks.load(Ordering::Relaxed),
+            self.inner.failed_health_checks.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Returns the number of reconnect attempts that failed on authentication.
+    pub(super) fn authentication_failures(&self) -> u64 {
+        self.inner.authentication_failures.load(Ordering::Relaxed)
+    }
+
+    /// Returns the server's capabilities, as negotiated when the connection was last established.
+    pub(super) fn server_capabilities(&self) -> Option<ServerCapabilities> {
+        self.inner.server_capabilities.read().unwrap().clone()
+    }
+
+    /// Queries the server's capabilities on the connection, and records them.
+    pub(super) async fn refresh_server_capabilities(&self) -> RedisResult<ServerCapabilities> {
+        let mut connection = self.get_connection().await?;
+        let result = query_server_capabilities(&mut connection).await;
+        *self.inner.server_capabilities.write().unwrap() = result.as_ref().ok().cloned();
+        result
+    }
+
+    /// Returns the number of successful reconnects, which may have connected to a different server behind the same
+    /// address.
+    pub(super) fn reconnections(&self) -> u64 {
+        self.inner.reconnections.load(Ordering::Relaxed)
+    }
+
     pub fn is_connected(&self) -> bool {
         !matches!(
             *self.inner.state.lock().unwrap(),
@@ -0,0 +1,125 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+// Commands scheduled to run once, after a delay or at a time, such as renewing a lease before its TTL expires.
+// Scheduled commands are kept in memory only - they're never persisted, and are cancelled when the client is closed.
+use super::Client;
+use logger_core::log_warn;
+use redis::cluster_routing::RoutingInfo;
+use redis::{Cmd, ErrorKind, RedisError, RedisResult, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tokio::task::{self, AbortHandle};
+
+/// The maximal number of commands that can be scheduled, and haven't run yet, per client.
+const MAX_SCHEDULED_COMMANDS: usize = 10_000;
+
+/// When a scheduled command runs.
+#[derive(Debug, Clone, Copy)]
+pub enum ScheduledTime {
+    After(Duration),
+    /// Times in the past run the command immediately.
+    At(SystemTime),
+}
+
+impl ScheduledTime {
+    fn delay(self) -> Duration {
+        match self {
+            ScheduledTime::After(delay) => delay,
+            ScheduledTime::At(time) => time.duration_since(SystemTime::now()).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct CommandScheduler {
+    next_id: AtomicU64,
+    /// The commands that haven't run yet, by ID.
+    pending: Mutex<HashMap<u64, AbortHandle>>,
+}
+
+impl CommandScheduler {
+    /// Removes the command from the pending commands, and returns its task if it was pending.
+    fn take(&self, id: u64) -> Option<AbortHandle> {
+        self.pending.lock().unwrap().remove(&id)
+    }
+}
+
+impl Client {
+    /// Runs the command once, after the scheduled time, and returns its ID. The command's response is discarded, and
+    /// failures are logged, so commands that must be confirmed shouldn't be scheduled.
+    pub fn schedule_command(
+        &self,
+        cmd: Cmd,
+        routing: Option<RoutingInfo>,
+        time: ScheduledTime,
+    ) -> RedisResult<Value> {
+        // The lock is held until the command is added, so that a command that runs immediately can't try to remove
+        // itself before it's added.
+        let mut pending = self.scheduler.pending.lock().unwrap();
+        if pending.len() >= MAX_SCHEDULED_COMMANDS {
+            return Err(RedisError::from((
+                ErrorKind::ClientError,
+                "Too many scheduled commands",
+                format!("at most {MAX_SCHEDULED_COMMANDS} commands can be pending"),
+            )));
+        }
+        let id = self.scheduler.next_id.fetch_add(1, Ordering::Relaxed);
+        let delay = time.delay();
+        let mut client = self.clone();
+        let task = task::spawn(async move {
+            tokio::time::sleep(delay).await;
+            // The command can't be cancelled once it's sent.
+            if client.scheduler.take(id).is_none() {
+                return;
+            }
+            if let Err(err) = client.send_command(&cmd, routing).await {
+                log_warn(
+                    "scheduled command",
+                    format!("Scheduled command {id} failed: {err}"),
+                );
+            }
+        });
+        pending.insert(id, task.abort_handle());
+        Ok(Value::Int(id as i64))
+    }
+
+    /// Cancels the scheduled command. Returns false if the command already ran, or was already cancelled.
+    pub fn cancel_scheduled_command(&self, id: u64) -> Value {
+        let Some(task) = self.scheduler.take(id) else {
+            return Value::Boolean(false);
+        };
+        task.abort();
+        Value::Boolean(true)
+    }
+
+    /// Cancels all of the commands that haven't run yet. Called when the client is closed, since the scheduled
+    /// commands hold clones of the client, and would otherwise keep its connections open until they run.
+    pub fn cancel_all_scheduled_commands(&self) {
+        for (_, task) in self.scheduler.pending.lock().unwrap().drain() {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScheduledTime;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_scheduled_time_delay() {
+        assert_eq!(
+            ScheduledTime::After(Duration::from_millis(100)).delay(),
+            Duration::from_millis(100)
+        );
+        let delay = ScheduledTime::At(SystemTime::now() + Duration::from_secs(10)).delay();
+        assert!(delay > Duration::from_secs(9) && delay <= Duration::from_secs(10));
+        assert_eq!(
+            ScheduledTime::At(SystemTime::now() - Duration::from_secs(10)).delay(),
+            Duration::ZERO
+        );
+    }
+}
@@ -0,0 +1,267 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::standalone_client::StandaloneClientConnectionError;
+use super::{get_connection_info, NodeAddress, TlsMode};
+use redis::aio::MultiplexedConnection;
+use redis::{ErrorKind, PushInfo, PushKind, RedisError, RedisResult, Value};
+use tokio::sync::mpsc;
+
+/// The Sentinel channel on which primary changes are published.
+const SWITCH_MASTER_CHANNEL: &str = "+switch-master";
+
+/// Replicas with these flags are not reachable, and aren't connected to.
+const UNAVAILABLE_REPLICA_FLAGS: [&str; 3] = ["s_down", "o_down", "disconnected"];
+
+async fn connect_to_sentinel(
+    sentinel: &NodeAddress,
+    tls_mode: TlsMode,
+    push_sender: Option<mpsc::UnboundedSender<PushInfo>>,
+) -> RedisResult<MultiplexedConnection> {
+    let redis_connection_info = redis::RedisConnectionInfo {
+        protocol: redis::ProtocolVersion::RESP3,
+        ..Default::default()
+    };
+    redis::Client::open(get_connection_info(
+        sentinel,
+        tls_mode,
+        redis_connection_info,
+    ))?
+    .get_multiplexed_async_connection(push_sender)
+    .await
+}
+
+/// Returns a connection to the sentinel which receives the primary changes it publishes as pushes.
+pub(super) async fn subscribe_to_primary_changes(
+    sentinel: &NodeAddress,
+    tls_mode: TlsMode,
+    push_sender: mpsc::UnboundedSender<PushInfo>,
+) -> RedisResult<MultiplexedConnection> {
+    let mut connection = connect_to_sentinel(sentinel, tls_mode, Some(push_sender)).await?;
+    let mut subscribe_cmd = redis::cmd("SUBSCRIBE");
+    subscribe_cmd.arg(SWITCH_MASTER_CHANNEL);
+    connection.send_packed_command(&subscribe_cmd).await?;
+    Ok(connection)
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::BulkString(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        Value::SimpleString(string) => Some(string.clone()),
+        _ => None,
+    }
+}
+
+fn invalid_response(command: &str) -> RedisError {
+    RedisError::from((
+        ErrorKind::TypeError,
+        "Unexpected Sentinel response",
+        command.to_string(),
+    ))
+}
+
+/// Parses the output of `SENTINEL GET-MASTER-ADDR-BY-NAME`, which is nil when the master name isn't monitored.
+fn parse_primary_address(value: Value) -> RedisResult<Option<NodeAddress>> {
+    let invalid_response = || invalid_response("SENTINEL GET-MASTER-ADDR-BY-NAME");
+    let values = match value {
+        Value::Nil => return Ok(None),
+        Value::Array(values) => values,
+        _ => return Err(invalid_response()),
+    };
+    let (Some(host), Some(port)) = (
+        values.first().and_then(value_to_string),
+        values.get(1).and_then(value_to_string),
+    ) else {
+        return Err(invalid_response());
+    };
+    let port = port.parse().map_err(|_| invalid_response())?;
+    Ok(Some(NodeAddress { host, port }))
+}
+
+/// Parses the output of `SENTINEL REPLICAS` into the addresses of the available replicas. Each replica is described
+/// by a map in RESP3, or by a flat array of fields and values in RESP2.
+fn parse_replica_addresses(value: Value) -> RedisResult<Vec<NodeAddress>> {
+    let invalid_response = || invalid_response("SENTINEL REPLICAS");
+    let Value::Array(replicas) = value else {
+        return Err(invalid_response());
+    };
+    let mut addresses = Vec::with_capacity(replicas.len());
+    for replica in replicas {
+        let fields: Vec<(Value, Value)> = match replica {
+            Value::Map(fields) => fields,
+            Value::Array(values) => {
+                let mut values = values.into_iter();
+                std::iter::from_fn(|| Some((values.next()?, values.next()?))).collect()
+            }
+            _ => return Err(invalid_response()),
+        };
+        let field = |name: &str| {
+            fields.iter().find_map(|(key, value)| {
+                (value_to_string(key).as_deref() == Some(name))
+                    .then(|| value_to_string(value))
+                    .flatten()
+            })
+        };
+        if field("flags").is_some_and(|flags| {
+            flags
+                .split(',')
+                .any(|flag| UNAVAILABLE_REPLICA_FLAGS.contains(&flag))
+        }) {
+            continue;
+        }
+        let (Some(host), Some(port)) = (field("ip"), field("port")) else {
+            return Err(invalid_response());
+        };
+        let port = port.parse().map_err(|_| invalid_response())?;
+        addresses.push(NodeAddress { host, port });
+    }
+    Ok(addresses)
+}
+
+async fn query_sentinel(
+    sentinel: &NodeAddress,
+    tls_mode: TlsMode,
+    master_name: &str,
+) -> RedisResult<Vec<NodeAddress>> {
+    let mut connection = connect_to_sentinel(sentinel, tls_mode, None).await?;
+    let mut primary_cmd = redis::cmd("SENTINEL");
+    primary_cmd.arg("GET-MASTER-ADDR-BY-NAME").arg(master_name);
+    let Some(primary) = parse_primary_address(connection.send_packed_command(&primary_cmd).await?)?
+    else {
+        return Err(RedisError::from((
+            ErrorKind::ClientError,
+            "Master name isn't monitored by the sentinel",
+            master_name.to_string(),
+        )));
+    };
+    let mut replicas_cmd = redis::cmd("SENTINEL");
+    replicas_cmd.arg("REPLICAS").arg(master_name);
+    let replicas = parse_replica_addresses(connection.send_packed_command(&replicas_cmd).await?)?;
+    Ok(std::iter::once(primary).chain(replicas).collect())
+}
+
+/// Queries the sentinels, in order, for the addresses of the primary and replicas of `master_name`, and returns the
+/// addresses of the first sentinel that answers, starting with the primary.
+pub(super) async fn discover_nodes(
+    sentinels: &[NodeAddress],
+    tls_mode: TlsMode,
+    master_name: &str,
+) -> Result<Vec<NodeAddress>, StandaloneClientConnectionError> {
+    if sentinels.is_empty() {
+        return Err(StandaloneClientConnectionError::NoAddressesProvided);
+    }
+    let mut addresses_and_errors = Vec::with_capacity(sentinels.len());
+    for sentinel in sentinels {
+        match query_sentinel(sentinel, tls_mode, master_name).await {
+            Ok(addresses) => return Ok(addresses),
+            Err(err) => addresses_and_errors
+                .push((Some(format!("{}:{}", sentinel.host, sentinel.port)), err)),
+        }
+    }
+    Err(StandaloneClientConnectionError::FailedConnection(
+        addresses_and_errors,
+    ))
+}
+
+/// Returns the address, formatted as `host:port`, of the new primary of `master_name` announced by the push, if it
+/// announces one. The message is formatted as `<master name> <old ip> <old port> <new ip> <new port>`.
+pub(super) fn new_primary_address(push_info: &PushInfo, master_name: &str) -> Option<String> {
+    if !matches!(push_info.kind, PushKind::Message) {
+        return None;
+    }
+    let (Some(channel), Some(message)) = (
+        push_info.data.first().and_then(value_to_string),
+        push_info.data.get(1).and_then(value_to_string),
+    ) else {
+        return None;
+    };
+    if channel != SWITCH_MASTER_CHANNEL {
+        return None;
+    }
+    let parts: Vec<&str> = message.split_whitespace().collect();
+    match parts.as_slice() {
+        [name, _, _, host, port] if *name == master_name => Some(format!("{host}:{port}")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{new_primary_address, parse_primary_address, parse_replica_addresses};
+    use redis::{PushInfo, PushKind, Value};
+
+    fn bulk(string: &str) -> Value {
+        Value::BulkString(string.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_primary_address() {
+        let primary = parse_primary_address(Value::Array(vec![bulk("10.0.0.1"), bulk("6379")]))
+            .unwrap()
+            .unwrap();
+        assert_eq!((primary.host.as_str(), primary.port), ("10.0.0.1", 6379));
+        assert!(parse_primary_address(Value::Nil).unwrap().is_none());
+        assert!(parse_primary_address(Value::Array(vec![bulk("10.0.0.1")])).is_err());
+    }
+
+    #[test]
+    fn test_parse_replica_addresses_skips_unavailable_replicas() {
+        let resp2_replica = Value::Array(vec![
+            bulk("ip"),
+            bulk("10.0.0.2"),
+            bulk("port"),
+            bulk("6380"),
+            bulk("flags"),
+            bulk("slave"),
+        ]);
+        let resp3_replica = Value::Map(vec![
+            (bulk("ip"), bulk("10.0.0.3")),
+            (bulk("port"), bulk("6381")),
+            (bulk("flags"), bulk("slave")),
+        ]);
+        let down_replica = Value::Map(vec![
+            (bulk("ip"), bulk("10.0.0.4")),
+            (bulk("port"), bulk("6382")),
+            (bulk("flags"), bulk("slave,s_down")),
+        ]);
+        let replicas = parse_replica_addresses(Value::Array(vec![
+            resp2_replica,
+            resp3_replica,
+            down_replica,
+        ]))
+        .unwrap();
+        assert_eq!(
+            replicas
+                .iter()
+                .map(|replica| (replica.host.as_str(), replica.port))
+                .collect::<Vec<_>>(),
+            vec![("10.0.0.2", 6380), ("10.0.0.3", 6381)]
+        );
+    }
+
+    #[test]
+    fn test_new_primary_address() {
+        let push = |channel: &str, message: &str| PushInfo {
+            kind: PushKind::Message,
+            data: vec![bulk(channel), bulk(message)],
+        };
+        assert_eq!(
+            new_primary_address(
+                &push("+switch-master", "mymaster 10.0.0.1 6379 10.0.0.2 6380"),
+                "mymaster"
+            ),
+            Some("10.0.0.2:6380".to_string())
+        );
+        assert_eq!(
+            new_primary_address(
+                &push("+switch-master", "other 10.0.0.1 6379 10.0.0.2 6380"),
+                "mymaster"
+            ),
+            None
+        );
+        assert_eq!(
+            new_primary_address(&push("+sdown", "mymaster 10.0.0.1 6379"), "mymaster"),
+            None
+        );
+    }
+}
@@ -0,0 +1,106 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::{Client, ClientWrapper, ReadFrom};
+use logger_core::{log_info, Level};
+use redis::{ErrorKind, RedisError, RedisResult, Value};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+/// Settings that can be changed on a live client. Unset settings keep their current value.
+#[derive(Debug, Default)]
+pub struct ClientSettingsUpdate {
+    pub request_timeout: Option<Duration>,
+    /// Supported only in standalone mode. Replicas are chosen with the replica selection strategy, and the availability
+    /// zone preference, the client was created with.
+    pub read_from: Option<ReadFrom>,
+    /// Applies to the logger of the whole process, rather than to a single client.
+    pub log_level: Option<Level>,
+}
+
+/// Returns the problems found in the update, which would prevent it from being applied.
+fn validate_settings_update(update: &ClientSettingsUpdate, cluster_mode: bool) -> Vec<String> {
+    let mut errors = Vec::new();
+    if update
+        .request_timeout
+        .is_some_and(|request_timeout| request_timeout.as_millis() == 0)
+    {
+        errors.push("the request timeout must be at least 1 millisecond".to_string());
+    }
+    if update.read_from.is_some() && cluster_mode {
+        errors.push("the read strategy can't be changed in cluster mode".to_string());
+    }
+    if matches!(update.read_from, Some(ReadFrom::AZAffinity(_))) {
+        errors.push("AZ affinity can only be set when the client is created".to_string());
+    }
+    errors
+}
+
+impl Client {
+    pub(super) fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout.load(Ordering::Relaxed))
+    }
+
+    /// Applies the update to the client and to all of its clones. The update is validated first, and if it's invalid
+    /// none of its settings are applied. Returns `OK` once the settings are applied.
+    pub fn update_settings(&self, update: ClientSettingsUpdate) -> RedisResult<Value> {
+        let errors = validate_settings_update(
+            &update,
+            matches!(self.internal_client, ClientWrapper::Cluster { .. }),
+        );
+        if !errors.is_empty() {
+            return Err(RedisError::from((
+                ErrorKind::ClientError,
+                "Invalid client settings",
+                errors.join(", "),
+            )));
+        }
+        log_info("client settings", format!("Updating settings: {update:?}"));
+        if let Some(request_timeout) = update.request_timeout {
+            self.request_timeout
+                .store(request_timeout.as_millis() as u64, Ordering::Relaxed);
+        }
+        if let (Some(read_from), ClientWrapper::Standalone(client)) =
+            (update.read_from, &self.internal_client)
+        {
+            client.set_read_from(read_from);
+        }
+        if let Some(log_level) = update.log_level {
+            logger_core::set_level(log_level);
+        }
+        Ok(Value::Okay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_settings_update, ClientSettingsUpdate};
+    use crate::client::ReadFrom;
+    use std::time::Duration;
+
+    #[test]
+    fn test_validate_settings_update() {
+        let update = ClientSettingsUpdate {
+            request_timeout: Some(Duration::from_millis(100)),
+            read_from: Some(ReadFrom::PreferReplica),
+            ..Default::default()
+        };
+        assert!(validate_settings_update(&update, false).is_empty());
+        assert_eq!(
+            validate_settings_update(&update, true),
+            vec!["the read strategy can't be changed in cluster mode"]
+        );
+    }
+
+    #[test]
+    fn test_validate_settings_update_rejects_zero_timeout() {
+        let update = ClientSettingsUpdate {
+            request_timeout: Some(Duration::from_micros(500)),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_settings_update(&update, false),
+            vec!["the request timeout must be at least 1 millisecond"]
+        );
+    }
+}
@@ -0,0 +1,285 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::command_keys::first_key;
+use super::statistics::value_size;
+use super::{Client, ConnectionError, ConnectionRequest};
+use logger_core::log_warn;
+use rand::Rng;
+use redis::cluster_routing::{is_readonly_cmd, Routable, RoutingInfo};
+use redis::{Cmd, PushInfo, RedisResult, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task;
+
+#[derive(Default)]
+struct ShadowStatistics {
+    shadowed_requests: AtomicU64,
+    shadow_errors: AtomicU64,
+    mismatches: AtomicU64,
+    primary_latency_total_us: AtomicU64,
+    shadow_latency_total_us: AtomicU64,
+}
+
+impl ShadowStatistics {
+    fn record(&self, primary_latency: Duration, shadow_latency: Duration, succeeded: bool) {
+        self.shadowed_requests.fetch_add(1, Ordering::Relaxed);
+        if !succeeded {
+            self.shadow_errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.primary_latency_total_us
+            .fetch_add(primary_latency.as_micros() as u64, Ordering::Relaxed);
+        self.shadow_latency_total_us
+            .fetch_add(shadow_latency.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn report(&self) -> Value {
+        let shadowed_requests = self.shadowed_requests.load(Ordering::Relaxed);
+        let average = |total: &AtomicU64| {
+            total
+                .load(Ordering::Relaxed)
+                .checked_div(shadowed_requests)
+                .unwrap_or_default() as i64
+        };
+        Value::Map(vec![
+            (
+                Value::SimpleString("shadowed_requests".to_string()),
+                Value::Int(shadowed_requests as i64),
+            ),
+            (
+                Value::SimpleString("shadow_errors".to_string()),
+                Value::Int(self.shadow_errors.load(Ordering::Relaxed) as i64),
+            ),
+            (
+                Value::SimpleString("mismatches".to_string()),
+                Value::Int(self.mismatches.load(Ordering::Relaxed) as i64),
+            ),
+            (
+                Value::SimpleString("average_primary_latency_us".to_string()),
+                Value::Int(average(&self.primary_latency_total_us)),
+            ),
+            (
+                Value::SimpleString("average_shadow_latency_us".to_string()),
+                Value::Int(average(&self.shadow_latency_total_us)),
+            ),
+        ])
+    }
+}
+
+/// Responses larger than this, in bytes, are compared by their hashes, to avoid keeping a copy of them.
+const COMPARE_BY_HASH_THRESHOLD: usize = 1024;
+
+fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+    std::mem::discriminant(value).hash(state);
+    match value {
+        Value::BulkString(bytes) => bytes.hash(state),
+        Value::SimpleString(string) => string.hash(state),
+        Value::VerbatimString { text, .. } => text.hash(state),
+        Value::Int(int) => int.hash(state),
+        Value::Double(double) => double.to_bits().hash(state),
+        Value::Boolean(boolean) => boolean.hash(state),
+        Value::Array(values) | Value::Set(values) => {
+            values.iter().for_each(|value| hash_value(value, state))
+        }
+        Value::Map(entries) => entries.iter().for_each(|(key, value)| {
+            hash_value(key, state);
+            hash_value(value, state);
+        }),
+        other => format!("{other:?}").hash(state),
+    }
+}
+
+/// The primary cluster's response, which the shadow's response is compared with.
+#[derive(Debug, PartialEq)]
+enum ExpectedResponse {
+    Value(Value),
+    Hash(u64),
+}
+
+impl ExpectedResponse {
+    fn new(value: &Value) -> Self {
+        if value_size(value) > COMPARE_BY_HASH_THRESHOLD {
+            let mut hasher = DefaultHasher::new();
+            hash_value(value, &mut hasher);
+            Self::Hash(hasher.finish())
+        } else {
+            Self::Value(value.clone())
+        }
+    }
+}
+
+/// Returns the probability of a read command to be shadowed. Percentages outside of 0..=100 are clamped, but a
+/// non-finite percentage is rejected, since it can't be clamped to a valid probability.
+fn shadow_probability(shadowed_percentage: f64) -> Result<f64, ConnectionError> {
+    if !shadowed_percentage.is_finite() {
+        return Err(ConnectionError::InvalidConfiguration(vec![format!(
+            "invalid shadowed percentage `{shadowed_percentage}`"
+        )]));
+    }
+    Ok((shadowed_percentage / 100.0).clamp(0.0, 1.0))
+}
+
+/// A client that duplicates a percentage of its read commands to a shadow cluster, in order to validate the shadow
+/// cluster before migrating to it. The shadow's responses are discarded, and its latencies are compared with those of
+/// the primary cluster in the client's statistics.
+#[derive(Clone)]
+pub struct ShadowingClient {
+    client: Client,
+    shadow: Client,
+    /// The probability of a read command to be shadowed, between 0 and 1.
+    shadow_probability: f64,
+    shadow_statistics: Arc<ShadowStatistics>,
+    /// Whether to compare the shadow's responses with the primary's, logging and counting mismatches.
+    verify_responses: bool,
+}
+
+impl ShadowingClient {
+    /// Connects to both clusters. `shadowed_percentage` is the percentage of read commands sent to the shadow cluster.
+    /// If `verify_responses` is set, the shadow's responses are compared with the primary's, which allows verifying
+    /// that the data is consistent during a migration.
+    pub async fn new(
+        request: ConnectionRequest,
+        shadow_request: ConnectionRequest,
+        shadowed_percentage: f64,
+        verify_responses: bool,
+        push_sender: Option<mpsc::UnboundedSender<PushInfo>>,
+    ) -> Result<Self, ConnectionError> {
+        let shadow_probability = shadow_probability(shadowed_percentage)?;
+        let client = Client::new(request, push_sender).await?;
+        let shadow = Client::new(shadow_request, None).await?;
+        Ok(Self {
+            client,
+            shadow,
+            shadow_probability,
+            shadow_statistics: Default::default(),
+            verify_responses,
+        })
+    }
+
+    /// Sends the command to the primary cluster, and possibly shadows it in the background.
+    /// The routing only applies to the primary cluster, since the shadow cluster's topology may differ.
+    pub async fn send_command(
+        &mut self,
+        cmd: &Cmd,
+        routing: Option<RoutingInfo>,
+    ) -> RedisResult<Value> {
+        let start = Instant::now();
+        let result = self.client.send_command(cmd, routing).await;
+        let primary_latency = start.elapsed();
+
+        let is_read = cmd
+            .command()
+            .is_some_and(|command| is_readonly_cmd(&command));
+        if is_read && rand::thread_rng().gen_bool(self.shadow_probability) {
+            let mut shadow = self.shadow.clone();
+            let shadow_statistics = self.shadow_statistics.clone();
+            let cmd = cmd.clone();
+            let expected = match &result {
+                Ok(value) if self.verify_responses => Some(ExpectedResponse::new(value)),
+                _ => None,
+            };
+            task::spawn(async move {
+                let start = Instant::now();
+                let shadow_result = shadow.send_command(&cmd, None).await;
+                shadow_statistics.record(primary_latency, start.elapsed(), shadow_result.is_ok());
+                if let (Some(expected), Ok(value)) = (expected, shadow_result) {
+                    if expected != ExpectedResponse::new(&value) {
+                        shadow_statistics.mismatches.fetch_add(1, Ordering::Relaxed);
+                        log_warn(
+                            "shadowing",
+                            format!(
+                                "Shadow response mismatch for key `{}`",
+                                first_key(&cmd)
+                                    .map(String::from_utf8_lossy)
+                                    .unwrap_or_default()
+                            ),
+                        );
+                    }
+                }
+            });
+        }
+        result
+    }
+
+    /// Returns the primary client's statistics, along with the shadowing statistics.
+    pub fn statistics(&self) -> Value {
+        let Value::Map(mut statistics) = self.client.statistics() else {
+            unreachable!("Client statistics are always a map");
+        };
+        statistics.push((
+            Value::SimpleString("shadow".to_string()),
+            self.shadow_statistics.report(),
+        ));
+        Value::Map(statistics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{shadow_probability, ExpectedResponse, ShadowStatistics};
+    use redis::Value;
+    use std::time::Duration;
+
+    #[test]
+    fn test_shadow_probability() {
+        assert_eq!(shadow_probability(25.0).unwrap(), 0.25);
+        assert_eq!(shadow_probability(150.0).unwrap(), 1.0);
+        assert_eq!(shadow_probability(-5.0).unwrap(), 0.0);
+        assert!(shadow_probability(f64::NAN).is_err());
+        assert!(shadow_probability(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_shadow_statistics_average_latencies() {
+        let statistics = ShadowStatistics::default();
+        statistics.record(Duration::from_micros(100), Duration::from_micros(300), true);
+        statistics.record(
+            Duration::from_micros(200),
+            Duration::from_micros(500),
+            false,
+        );
+        assert_eq!(
+            statistics.report(),
+            Value::Map(vec![
+                (
+                    Value::SimpleString("shadowed_requests".to_string()),
+                    Value::Int(2)
+                ),
+                (
+                    Value::SimpleString("shadow_errors".to_string()),
+                    Value::Int(1)
+                ),
+                (Value::SimpleString("mismatches".to_string()), Value::Int(0)),
+                (
+                    Value::SimpleString("average_primary_latency_us".to_string()),
+                    Value::Int(150)
+                ),
+                (
+                    Value::SimpleString("average_shadow_latency_us".to_string()),
+                    Value::Int(400)
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_large_responses_are_compared_by_hash() {
+        let small = Value::BulkString(b"value".to_vec());
+        assert_eq!(
+            ExpectedResponse::new(&small),
+            ExpectedResponse::Value(small)
+        );
+
+        let large = Value::Array(vec![Value::BulkString(vec![b'a'; 2048])]);
+        let expected = ExpectedResponse::new(&large);
+        assert!(matches!(expected, ExpectedResponse::Hash(_)));
+        assert_eq!(expected, ExpectedResponse::new(&large));
+
+        let different = Value::Array(vec![Value::BulkString(vec![b'b'; 2048])]);
+        assert_ne!(expected, ExpectedResponse::new(&different));
+    }
+}
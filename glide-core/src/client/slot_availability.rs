@@ -0,0 +1,219 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::broadcast::parse_cluster_nodes_response;
+use super::{run_with_timeout, Client, ClientWrapper};
+use crate::errors::SLOT_UNAVAILABLE_ERROR_CODE;
+use logger_core::log_warn;
+use redis::cluster_routing::{RoutingInfo, SingleNodeRoutingInfo};
+use redis::RedisResult;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::task;
+
+/// How long the known unavailable slots are used before they're refreshed.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tracks the slots whose primaries are marked as failed by the cluster, so that commands routed to them fail fast
+/// instead of being retried until they time out, while commands to other slots are still served.
+#[derive(Default)]
+pub(super) struct SlotAvailability {
+    /// Slot ranges owned by failed primaries, with the primary's address.
+    unavailable_ranges: RwLock<Vec<(u16, u16, String)>>,
+    refreshed_at: Mutex<Option<Instant>>,
+    refreshing: AtomicBool,
+}
+
+impl SlotAvailability {
+    /// Fails if the routing targets a slot owned by a failed primary.
+    pub(super) fn check(&self, routing: &RoutingInfo) -> RedisResult<()> {
+        let RoutingInfo::SingleNode(SingleNodeRoutingInfo::SpecificNode(route)) = routing else {
+            return Ok(());
+        };
+        let slot = route.slot();
+        let unavailable_ranges = self.unavailable_ranges.read().unwrap();
+        match unavailable_ranges
+            .iter()
+            .find(|(start, end, _)| (*start..=*end).contains(&slot))
+        {
+            Some((start, end, address)) => Err(redis::make_extension_error(
+                SLOT_UNAVAILABLE_ERROR_CODE.to_string(),
+                Some(format!(
+                    "slots {start}-{end} are owned by the failed node {address}"
+                )),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Marks a refresh as started, if the known unavailable slots are stale and no other refresh is running.
+    fn start_refresh(&self) -> bool {
+        let is_stale = self
+            .refreshed_at
+            .lock()
+            .unwrap()
+            .map_or(true, |refreshed_at| {
+                refreshed_at.elapsed() >= REFRESH_INTERVAL
+            });
+        is_stale && !self.refreshing.swap(true, Ordering::Relaxed)
+    }
+
+    fn finish_refresh(&self, unavailable_ranges: Option<Vec<(u16, u16, String)>>) {
+        if let Some(unavailable_ranges) = unavailable_ranges {
+            *self.unavailable_ranges.write().unwrap() = unavailable_ranges;
+        }
+        *self.refreshed_at.lock().unwrap() = Some(Instant::now());
+        self.refreshing.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Parses the output of `CLUSTER NODES` into the slot ranges owned by failed primaries.
+fn parse_unavailable_slots(cluster_nodes: &str) -> Vec<(u16, u16, String)> {
+    let mut unavailable_ranges = Vec::new();
+    for line in cluster_nodes.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let (Some(address), Some(flags)) = (parts.get(1), parts.get(2)) else {
+            continue;
+        };
+        let flags: Vec<&str> = flags.split(',').collect();
+        if !flags.contains(&"master") || !flags.contains(&"fail") {
+            continue;
+        }
+        // The address is formatted as `host:port@cport[,hostname]`.
+        let address = address.split('@').next().unwrap_or_default();
+        // Slots start at the ninth field. Migrating and importing slots are formatted as `[slot->-node]`, and are
+        // skipped since their primary is the node they're migrating to.
+        for slots in parts.iter().skip(8).filter(|slots| !slots.starts_with('[')) {
+            let range = match slots.split_once('-') {
+                Some((start, end)) => start.parse().ok().zip(end.parse().ok()),
+                None => slots.parse().ok().map(|slot| (slot, slot)),
+            };
+            if let Some((start, end)) = range {
+                unavailable_ranges.push((start, end, address.to_string()));
+            }
+        }
+    }
+    unavailable_ranges
+}
+
+impl Client {
+    /// Refreshes the unavailable slots in the background, if they're stale.
+    pub(super) fn refresh_slot_availability_if_stale(&self) {
+        let Some(slot_availability) = self.slot_availability.clone() else {
+            return;
+        };
+        if !slot_availability.start_refresh() {
+            return;
+        }
+        // The refresh is sent on the cluster connection directly, so that it isn't counted in the client's statistics,
+        // and doesn't take a permit from its concurrency limits.
+        let ClientWrapper::Cluster { mut client } = self.internal_client.clone() else {
+            return;
+        };
+        let request_timeout = self.request_timeout();
+        task::spawn(async move {
+            let mut cluster_nodes_cmd = redis::cmd("CLUSTER");
+            cluster_nodes_cmd.arg("NODES");
+            let cluster_nodes = run_with_timeout(
+                Some(request_timeout),
+                client.route_command(
+                    &cluster_nodes_cmd,
+                    RoutingInfo::SingleNode(SingleNodeRoutingInfo::Random),
+                ),
+            )
+            .await
+            .and_then(parse_cluster_nodes_response);
+            let unavailable_ranges = match cluster_nodes {
+                Ok(cluster_nodes) => Some(parse_unavailable_slots(&cluster_nodes)),
+                Err(err) => {
+                    log_warn(
+                        "slot availability",
+                        format!("Failed to refresh the unavailable slots: {err}"),
+                    );
+                    None
+                }
+            };
+            slot_availability.finish_refresh(unavailable_ranges);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_unavailable_slots, SlotAvailability};
+    use crate::errors::{error_type, RequestErrorType, SLOT_UNAVAILABLE_ERROR_CODE};
+    use redis::cluster_routing::{Route, RoutingInfo, SingleNodeRoutingInfo, SlotAddr};
+    use redis::{ErrorKind, RedisError};
+
+    const CLUSTER_NODES: &str = "\
+07c37dfeb235213a872192d90877d0cd55635b91 127.0.0.1:30004@31004 slave,fail e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 0 1426238317239 4 connected
+67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1 127.0.0.1:30002@31002 master,fail - 0 1426238316232 2 disconnected 5461-10921 10922 [10923->-292f8b365bb7edb5e285caf0b7e6ddc7265d2f4f]
+292f8b365bb7edb5e285caf0b7e6ddc7265d2f4f 127.0.0.1:30003@31003 master - 0 1426238318243 3 connected 10923-16383
+e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 127.0.0.1:30001@31001 myself,master - 0 0 1 connected 0-5460
+";
+
+    fn route_to_slot(slot: u16) -> RoutingInfo {
+        RoutingInfo::SingleNode(SingleNodeRoutingInfo::SpecificNode(Route::new(
+            slot,
+            SlotAddr::Master,
+        )))
+    }
+
+    #[test]
+    fn test_parse_unavailable_slots_of_failed_primaries() {
+        assert_eq!(
+            parse_unavailable_slots(CLUSTER_NODES),
+            vec![
+                (5461, 10921, "127.0.0.1:30002".to_string()),
+                (10922, 10922, "127.0.0.1:30002".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_commands_to_unavailable_slots_fail_fast() {
+        let slot_availability = SlotAvailability::default();
+        slot_availability.finish_refresh(Some(parse_unavailable_slots(CLUSTER_NODES)));
+
+        let err = slot_availability.check(&route_to_slot(6000)).unwrap_err();
+        assert_eq!(err.code(), Some(SLOT_UNAVAILABLE_ERROR_CODE));
+        assert!(err.to_string().contains("slots 5461-10921"));
+        assert!(slot_availability.check(&route_to_slot(100)).is_ok());
+        assert!(slot_availability
+            .check(&RoutingInfo::SingleNode(SingleNodeRoutingInfo::Random))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_only_client_errors_are_reported_as_slot_unavailable() {
+        let slot_availability = SlotAvailability::default();
+        slot_availability.finish_refresh(Some(parse_unavailable_slots(CLUSTER_NODES)));
+        let client_error = slot_availability.check(&route_to_slot(6000)).unwrap_err();
+        assert!(matches!(
+            error_type(&client_error),
+            RequestErrorType::SlotUnavailable
+        ));
+
+        let server_error = RedisError::from((
+            ErrorKind::ClusterDown,
+            "An error was signalled by the server",
+            "The cluster is down".to_string(),
+        ));
+        assert!(matches!(
+            error_type(&server_error),
+            RequestErrorType::Unspecified
+        ));
+    }
+
+    #[test]
+    fn test_refresh_starts_only_when_stale() {
+        let slot_availability = SlotAvailability::default();
+        assert!(slot_availability.start_refresh());
+        // A refresh is already running.
+        assert!(!slot_availability.start_refresh());
+        slot_availability.finish_refresh(None);
+        // The refresh just finished.
+        assert!(!slot_availability.start_refresh());
+    }
+}
@@ -3,35 +3,100 @@
  */
 use super::get_redis_connection_info;
 use super::reconnecting_connection::ReconnectingConnection;
-use super::{ConnectionRequest, NodeAddress, TlsMode};
+use super::sentinel;
+use super::{
+    ConnectionPoolDispatch, ConnectionRequest, NodeAddress, ReplicaLagCheck, ReplicaSelection,
+    ServerCapabilities, TlsMode,
+};
 use crate::retry_strategies::RetryStrategy;
-use futures::{future, stream, StreamExt};
+use futures::{future, stream, FutureExt, StreamExt};
 #[cfg(standalone_heartbeat)]
 use logger_core::log_debug;
 use logger_core::log_warn;
 use rand::Rng;
 use redis::cluster_routing::{self, is_readonly_cmd, ResponsePolicy, Routable, RoutingInfo};
-use redis::{PushInfo, RedisError, RedisResult, Value};
-use std::sync::atomic::AtomicUsize;
-use std::sync::Arc;
+use redis::{PushInfo, PushKind, RedisError, RedisResult, Value};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock, Weak};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
-#[cfg(standalone_heartbeat)]
 use tokio::task;
 
 #[derive(Debug)]
-enum ReadFrom {
-    Primary,
-    PreferReplica {
+enum ReplicaSelector {
+    RoundRobin {
         latest_read_replica_index: Arc<std::sync::atomic::AtomicUsize>,
     },
+    Random,
+    LeastOutstandingRequests,
+    /// Weights are indexed like the client's nodes.
+    Weighted(Vec<u32>),
+    PowerOfTwoChoices,
+}
+
+/// Additional connections to a node, which requests are dispatched to along with the node's connection.
+#[derive(Debug)]
+struct ConnectionPool {
+    connections: Vec<ReconnectingConnection>,
+    latest_index: AtomicUsize,
 }
 
 #[derive(Debug)]
 struct DropWrapper {
-    /// Connection to the primary node in the client.
-    primary_index: usize,
+    /// Connection to the primary node in the client. Updated when a sentinel announces a failover.
+    primary_index: AtomicUsize,
     nodes: Vec<ReconnectingConnection>,
-    read_from: ReadFrom,
+    /// Whether reads are sent to replicas. Can be changed on a live client.
+    reads_from_replicas: AtomicBool,
+    replica_selector: ReplicaSelector,
+    /// When set, replicas in this availability zone are preferred.
+    client_az: Option<String>,
+    /// The availability zone of each node, indexed like `nodes`. Only fetched when `client_az` is set, and refreshed
+    /// when a node reconnects or a sentinel announces a failover.
+    availability_zones: Vec<RwLock<Option<String>>>,
+    /// Latest estimation of the replication lag of each node, in bytes, indexed like `nodes`.
+    replica_lags: Vec<AtomicU64>,
+    /// Replicas lagging by more than this value are skipped when reading from replicas.
+    max_replica_lag: Option<u64>,
+    /// Indexed like `nodes`.
+    pools: Vec<ConnectionPool>,
+    pool_dispatch: ConnectionPoolDispatch,
+}
+
+impl DropWrapper {
+    fn primary_index(&self) -> usize {
+        self.primary_index.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of requests awaiting a response from the node, on all of its connections.
+    fn node_inflight_requests(&self, index: usize) -> usize {
+        self.nodes[index].inflight_requests()
+            + self.pools[index]
+                .connections
+                .iter()
+                .map(ReconnectingConnection::inflight_requests)
+                .sum::<usize>()
+    }
+
+    /// Returns the connection of the node that a request should be sent on, according to the pool dispatch strategy.
+    fn pooled_connection(&self, index: usize) -> &ReconnectingConnection {
+        let node = &self.nodes[index];
+        let pool = &self.pools[index];
+        if pool.connections.is_empty() {
+            return node;
+        }
+        let mut connections = std::iter::once(node).chain(pool.connections.iter());
+        match self.pool_dispatch {
+            ConnectionPoolDispatch::RoundRobin => {
+                let position = pool.latest_index.fetch_add(1, Ordering::Relaxed)
+                    % (pool.connections.len() + 1);
+                connections.nth(position).unwrap_or(node)
+            }
+            ConnectionPoolDispatch::LeastInflight => connections
+                .min_by_key(|connection| connection.inflight_requests())
+                .unwrap_or(node),
+        }
+    }
 }
 
 impl Drop for DropWrapper {
@@ -39,6 +104,9 @@ impl Drop for DropWrapper {
         for node in self.nodes.iter() {
             node.mark_as_dropped();
         }
+        for connection in self.pools.iter().flat_map(|pool| pool.connections.iter()) {
+            connection.mark_as_dropped();
+        }
     }
 }
 
@@ -113,28 +181,41 @@ impl StandaloneClient {
         // randomize pubsub nodes, maybe a batter option is to always use the primary
         let pubsub_node_index = rand::thread_rng().gen_range(0..node_count);
         let pubsub_addr = &connection_request.addresses[pubsub_node_index];
-        let mut stream = stream::iter(connection_request.addresses.iter())
-            .map(|address| async {
-                get_connection_and_replication_info(
-                    address,
-                    &retry_strategy,
-                    if address.to_string() != pubsub_addr.to_string() {
-                        &redis_connection_info
-                    } else {
-                        &pubsub_connection_info
-                    },
-                    tls_mode.unwrap_or(TlsMode::NoTls),
-                    &push_sender,
-                )
-                .await
-                .map_err(|err| (format!("{}:{}", address.host, address.port), err))
+        let subscriber_only = connection_request.subscriber_only;
+        // In subscriber only mode, only the node that holds the subscriptions is connected.
+        let addresses = connection_request
+            .addresses
+            .iter()
+            .enumerate()
+            .filter(|(address_index, _)| !subscriber_only || *address_index == pubsub_node_index);
+        let mut stream = stream::iter(addresses)
+            .map(|(address_index, address)| {
+                async {
+                    get_connection_and_replication_info(
+                        address,
+                        &retry_strategy,
+                        if address.to_string() != pubsub_addr.to_string() {
+                            &redis_connection_info
+                        } else {
+                            &pubsub_connection_info
+                        },
+                        tls_mode.unwrap_or(TlsMode::NoTls),
+                        &push_sender,
+                    )
+                    .await
+                    .map_err(|err| (format!("{}:{}", address.host, address.port), err))
+                }
+                .map(move |result| (address_index, result))
             })
             .buffer_unordered(node_count);
 
         let mut nodes = Vec::with_capacity(node_count);
+        // The index of each node in the connection request's addresses, since the connections are created out of order.
+        let mut address_indices = Vec::with_capacity(node_count);
         let mut addresses_and_errors = Vec::with_capacity(node_count);
         let mut primary_index = None;
-        while let Some(result) = stream.next().await {
+        while let Some((address_index, result)) = stream.next().await {
+            address_indices.push(address_index);
             match result {
                 Ok((connection, replication_status)) => {
                     nodes.push(connection);
@@ -161,6 +242,15 @@ impl StandaloneClient {
             }
         }
 
+        if subscriber_only {
+            if !addresses_and_errors.is_empty() {
+                return Err(StandaloneClientConnectionError::FailedConnection(
+                    addresses_and_errors,
+                ));
+            }
+            // The single connection is used for all requests, regardless of the node's role.
+            primary_index = Some(0);
+        }
         let Some(primary_index) = primary_index else {
             if addresses_and_errors.is_empty() {
                 addresses_and_errors.insert(
@@ -183,30 +273,81 @@ impl StandaloneClient {
                 ),
             );
         }
-        let read_from = get_read_from(connection_request.read_from);
+        let reads_from_replicas = matches!(
+            connection_request.read_from,
+            Some(super::ReadFrom::PreferReplica | super::ReadFrom::AZAffinity(_))
+        );
+        let client_az = match &connection_request.read_from {
+            Some(super::ReadFrom::AZAffinity(az)) => Some(az.clone()),
+            _ => None,
+        };
+        let availability_zones = if client_az.is_some() {
+            future::join_all(nodes.iter().map(get_availability_zone)).await
+        } else {
+            vec![None; nodes.len()]
+        };
+        let availability_zones = availability_zones.into_iter().map(RwLock::new).collect();
+        let replica_selector =
+            get_replica_selector(connection_request.replica_selection, &address_indices);
+
+        // The subscriptions are held by a single connection, so there's no use for more connections.
+        let pool_size = if subscriber_only {
+            0
+        } else {
+            connection_request.connections_per_node.saturating_sub(1) as usize
+        };
+        let pools = future::join_all(address_indices.iter().map(|address_index| {
+            create_connection_pool(
+                &connection_request.addresses[*address_index],
+                pool_size,
+                &retry_strategy,
+                &redis_connection_info,
+                tls_mode.unwrap_or(TlsMode::NoTls),
+            )
+        }))
+        .await;
 
         #[cfg(standalone_heartbeat)]
-        for node in nodes.iter() {
+        for node in nodes
+            .iter()
+            .chain(pools.iter().flat_map(|pool| pool.connections.iter()))
+        {
             Self::start_heartbeat(node.clone());
         }
 
-        Ok(Self {
-            inner: Arc::new(DropWrapper {
-                primary_index,
-                nodes,
-                read_from,
-            }),
-        })
+        let replica_lags = nodes.iter().map(|_| AtomicU64::new(0)).collect();
+        let max_replica_lag = connection_request
+            .replica_lag_check
+            .as_ref()
+            .and_then(|replica_lag_check| replica_lag_check.max_lag);
+        let inner = Arc::new(DropWrapper {
+            primary_index: AtomicUsize::new(primary_index),
+            nodes,
+            reads_from_replicas: AtomicBool::new(reads_from_replicas),
+            replica_selector,
+            client_az,
+            availability_zones,
+            replica_lags,
+            max_replica_lag,
+            pools,
+            pool_dispatch: connection_request.connection_pool_dispatch,
+        });
+        if let Some(ReplicaLagCheck { interval, .. }) = connection_request.replica_lag_check {
+            Self::start_replica_lag_sampler(Arc::downgrade(&inner), interval);
+        }
+        if inner.client_az.is_some() {
+            Self::start_availability_zone_refresher(&inner);
+        }
+
+        Ok(Self { inner })
     }
 
     fn get_primary_connection(&self) -> &ReconnectingConnection {
-        self.inner.nodes.get(self.inner.primary_index).unwrap()
+        self.inner.nodes.get(self.inner.primary_index()).unwrap()
     }
 
-    fn round_robin_read_from_replica(
-        &self,
-        latest_read_replica_index: &Arc<AtomicUsize>,
-    ) -> &ReconnectingConnection {
+    fn round_robin_read_from_replica(&self, latest_read_replica_index: &Arc<AtomicUsize>) -> usize {
+        let local_only = self.prefers_local_replicas();
         let initial_index = latest_read_replica_index.load(std::sync::atomic::Ordering::Relaxed);
         let mut check_count = 0;
         loop {
@@ -214,46 +355,222 @@ impl StandaloneClient {
 
             // Looped through all replicas, no connected replica was found.
             if check_count > self.inner.nodes.len() {
-                return self.get_primary_connection();
+                return self.inner.primary_index();
             }
             let index = (initial_index + check_count) % self.inner.nodes.len();
-            if index == self.inner.primary_index {
+            if !self.is_selectable_replica(index, local_only) {
                 continue;
             }
-            let Some(connection) = self.inner.nodes.get(index) else {
-                continue;
-            };
-            if connection.is_connected() {
-                let _ = latest_read_replica_index.compare_exchange_weak(
-                    initial_index,
-                    index,
-                    std::sync::atomic::Ordering::Relaxed,
-                    std::sync::atomic::Ordering::Relaxed,
-                );
-                return connection;
+            let _ = latest_read_replica_index.compare_exchange_weak(
+                initial_index,
+                index,
+                std::sync::atomic::Ordering::Relaxed,
+                std::sync::atomic::Ordering::Relaxed,
+            );
+            return index;
+        }
+    }
+
+    fn is_lagging_replica(&self, index: usize) -> bool {
+        self.inner
+            .max_replica_lag
+            .is_some_and(|max_lag| self.inner.replica_lags[index].load(Ordering::Relaxed) > max_lag)
+    }
+
+    /// Returns whether the node at `index` is a connected replica that can serve reads.
+    fn is_available_replica(&self, index: usize) -> bool {
+        index != self.inner.primary_index()
+            && self.inner.nodes[index].is_connected()
+            && !self.is_lagging_replica(index)
+    }
+
+    fn is_local_node(&self, index: usize) -> bool {
+        self.inner.client_az.is_some()
+            && *self.inner.availability_zones[index].read().unwrap() == self.inner.client_az
+    }
+
+    /// Returns whether reads should be restricted to replicas in the client's availability zone, which is the case
+    /// when at least one of them can serve reads.
+    fn prefers_local_replicas(&self) -> bool {
+        (0..self.inner.nodes.len())
+            .any(|index| self.is_local_node(index) && self.is_available_replica(index))
+    }
+
+    fn is_selectable_replica(&self, index: usize, local_only: bool) -> bool {
+        self.is_available_replica(index) && (!local_only || self.is_local_node(index))
+    }
+
+    /// Returns the replicas that can serve reads, limited to the client's availability zone when possible.
+    fn available_replicas(&self) -> impl Iterator<Item = usize> + '_ {
+        let local_only = self.prefers_local_replicas();
+        (0..self.inner.nodes.len())
+            .filter(move |index| self.is_selectable_replica(*index, local_only))
+    }
+
+    fn random_read_from_replica(&self) -> usize {
+        let replicas: Vec<usize> = self.available_replicas().collect();
+        if replicas.is_empty() {
+            return self.inner.primary_index();
+        }
+        replicas[rand::thread_rng().gen_range(0..replicas.len())]
+    }
+
+    fn least_outstanding_read_from_replica(&self) -> usize {
+        self.available_replicas()
+            .min_by_key(|index| self.inner.node_inflight_requests(*index))
+            .unwrap_or_else(|| self.inner.primary_index())
+    }
+
+    fn power_of_two_choices_read_from_replica(&self) -> usize {
+        let replicas: Vec<usize> = self.available_replicas().collect();
+        match replicas.len() {
+            0 => self.inner.primary_index(),
+            1 => replicas[0],
+            _ => {
+                let [first, second] = two_random_indices(replicas.len());
+                let (first, second) = (replicas[first], replicas[second]);
+                if self.inner.node_inflight_requests(second)
+                    < self.inner.node_inflight_requests(first)
+                {
+                    second
+                } else {
+                    first
+                }
             }
         }
     }
 
-    fn get_connection(&self, readonly: bool) -> &ReconnectingConnection {
-        if self.inner.nodes.len() == 1 || !readonly {
-            return self.get_primary_connection();
+    fn weighted_read_from_replica(&self, weights: &[u32]) -> usize {
+        let replicas: Vec<(usize, u64)> = self
+            .available_replicas()
+            .map(|index| (index, weights[index] as u64))
+            .filter(|(_, weight)| *weight > 0)
+            .collect();
+        let total_weight: u64 = replicas.iter().map(|(_, weight)| weight).sum();
+        if total_weight == 0 {
+            return self.inner.primary_index();
+        }
+        let mut chosen_weight = rand::thread_rng().gen_range(0..total_weight);
+        for (index, weight) in replicas {
+            if chosen_weight < weight {
+                return index;
+            }
+            chosen_weight -= weight;
+        }
+        self.inner.primary_index()
+    }
+
+    /// Returns the latest replication lag estimation, in bytes, of every replica in the client.
+    /// The estimations are updated only when a replica lag check is configured in the connection request.
+    pub fn replica_lags(&self) -> Vec<(String, u64)> {
+        self.inner
+            .nodes
+            .iter()
+            .zip(self.inner.replica_lags.iter())
+            .enumerate()
+            .filter(|(index, _)| *index != self.inner.primary_index())
+            .map(|(_, (node, lag))| (node.node_address(), lag.load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    /// Returns the number of requests awaiting a response from each node, the moving averages of its latency and
+    /// connection error rate, and its health check and authentication failure counts, as an array of maps.
+    pub fn node_statistics(&self) -> Value {
+        Value::Array(
+            self.inner
+                .nodes
+                .iter()
+                .enumerate()
+                .map(|(index, node)| {
+                    let (health_checks, failed_health_checks) = node.health_checks();
+                    Value::Map(vec![
+                        (
+                            Value::SimpleString("address".to_string()),
+                            Value::BulkString(node.node_address().into_bytes()),
+                        ),
+                        (
+                            Value::SimpleString("inflight_requests".to_string()),
+                            Value::Int(self.inner.node_inflight_requests(index) as i64),
+                        ),
+                        (
+                            Value::SimpleString("latency_average_us".to_string()),
+                            Value::Int(node.latency_average().as_micros() as i64),
+                        ),
+                        (
+                            Value::SimpleString("error_rate_average".to_string()),
+                            Value::Double(node.error_rate_average()),
+                        ),
+                        (
+                            Value::SimpleString("health_checks".to_string()),
+                            Value::Int(health_checks as i64),
+                        ),
+                        (
+                            Value::SimpleString("failed_health_checks".to_string()),
+                            Value::Int(failed_health_checks as i64),
+                        ),
+                        (
+                            Value::SimpleString("authentication_failures".to_string()),
+                            Value::Int(node.authentication_failures() as i64),
+                        ),
+                    ])
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the index of the node that should serve the request.
+    fn get_node_index(&self, readonly: bool) -> usize {
+        if self.inner.nodes.len() == 1
+            || !readonly
+            || !self.inner.reads_from_replicas.load(Ordering::Relaxed)
+        {
+            return self.inner.primary_index();
         }
 
-        match &self.inner.read_from {
-            ReadFrom::Primary => self.get_primary_connection(),
-            ReadFrom::PreferReplica {
+        match &self.inner.replica_selector {
+            ReplicaSelector::RoundRobin {
                 latest_read_replica_index,
             } => self.round_robin_read_from_replica(latest_read_replica_index),
+            ReplicaSelector::Random => self.random_read_from_replica(),
+            ReplicaSelector::LeastOutstandingRequests => self.least_outstanding_read_from_replica(),
+            ReplicaSelector::Weighted(weights) => self.weighted_read_from_replica(weights),
+            ReplicaSelector::PowerOfTwoChoices => self.power_of_two_choices_read_from_replica(),
         }
     }
 
+    /// Changes whether reads are sent to replicas, using the replica selection strategy the client was created with.
+    pub(super) fn set_read_from(&self, read_from: super::ReadFrom) {
+        self.inner
+            .reads_from_replicas
+            .store(read_from != super::ReadFrom::Primary, Ordering::Relaxed);
+    }
+
+    /// Returns the capabilities negotiated by the primary's connection when it was established.
+    pub(super) fn server_capabilities(&self) -> Option<ServerCapabilities> {
+        self.get_primary_connection().server_capabilities()
+    }
+
+    /// Queries the capabilities of the primary on its connection, and records them.
+    pub(super) async fn refresh_server_capabilities(&self) -> RedisResult<ServerCapabilities> {
+        self.get_primary_connection()
+            .refresh_server_capabilities()
+            .await
+    }
+
     async fn send_request(
         cmd: &redis::Cmd,
         reconnecting_connection: &ReconnectingConnection,
     ) -> RedisResult<Value> {
+        let _inflight_guard = reconnecting_connection.track_inflight_request();
         let mut connection = reconnecting_connection.get_connection().await?;
+        let start = Instant::now();
         let result = connection.send_packed_command(cmd).await;
+        reconnecting_connection.record_request(
+            start.elapsed(),
+            result
+                .as_ref()
+                .is_err_and(|err| err.is_io_error() || err.is_unrecoverable_error()),
+        );
         match result {
             Err(err) if err.is_unrecoverable_error() => {
                 log_warn("send request", format!("received disconnect error `{err}`"));
@@ -322,7 +639,7 @@ impl StandaloneClient {
         cmd: &redis::Cmd,
         readonly: bool,
     ) -> RedisResult<Value> {
-        let reconnecting_connection = self.get_connection(readonly);
+        let reconnecting_connection = self.inner.pooled_connection(self.get_node_index(readonly));
         Self::send_request(cmd, reconnecting_connection).await
     }
 
@@ -331,6 +648,11 @@ impl StandaloneClient {
             return self.send_request_to_single_node(cmd, false).await;
         };
 
+        // Watched keys belong to a connection, so they're sent on the connection that sends transactions.
+        if matches!(cmd_bytes.as_slice(), b"WATCH" | b"UNWATCH") {
+            return Self::send_request(cmd, self.get_primary_connection()).await;
+        }
+
         if RoutingInfo::is_all_nodes(cmd_bytes.as_slice()) {
             let response_policy = ResponsePolicy::for_command(cmd_bytes.as_slice());
             return self.send_request_to_all_nodes(cmd, response_policy).await;
@@ -345,11 +667,25 @@ impl StandaloneClient {
         offset: usize,
         count: usize,
     ) -> RedisResult<Vec<Value>> {
-        let reconnecting_connection = self.get_primary_connection();
+        let primary_index = self.inner.primary_index();
+        // Transactions are sent on the primary's first connection, which holds the watched keys.
+        let reconnecting_connection = if pipeline.is_transaction() {
+            &self.inner.nodes[primary_index]
+        } else {
+            self.inner.pooled_connection(primary_index)
+        };
+        let _inflight_guard = reconnecting_connection.track_inflight_request();
         let mut connection = reconnecting_connection.get_connection().await?;
+        let start = Instant::now();
         let result = connection
             .send_packed_commands(pipeline, offset, count)
             .await;
+        reconnecting_connection.record_request(
+            start.elapsed(),
+            result
+                .as_ref()
+                .is_err_and(|err| err.is_io_error() || err.is_unrecoverable_error()),
+        );
         match result {
             Err(err) if err.is_unrecoverable_error() => {
                 log_warn(
@@ -363,9 +699,140 @@ impl StandaloneClient {
         }
     }
 
+    fn start_replica_lag_sampler(inner: Weak<DropWrapper>, interval: std::time::Duration) {
+        task::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(inner) = inner.upgrade() else {
+                    // Client was dropped, sampling can stop.
+                    return;
+                };
+                let primary_index = inner.primary_index();
+                let primary = &inner.nodes[primary_index];
+                let Some(primary_offset) = get_replication_offset(primary).await else {
+                    continue;
+                };
+                for (index, node) in inner.nodes.iter().enumerate() {
+                    if index == primary_index {
+                        continue;
+                    }
+                    // Unreachable replicas keep their previous estimation.
+                    if let Some(replica_offset) = get_replication_offset(node).await {
+                        inner.replica_lags[index].store(
+                            primary_offset.saturating_sub(replica_offset),
+                            Ordering::Relaxed,
+                        );
+                    }
+                }
+            }
+        });
+    }
+
+    /// Reads the availability zones of the nodes that reconnected, since the server behind a node's address may have
+    /// been replaced by a server in another zone, and of all nodes after a sentinel announces a failover.
+    fn start_availability_zone_refresher(inner: &Arc<DropWrapper>) {
+        let mut known_primary_index = inner.primary_index();
+        let mut known_reconnections: Vec<u64> = inner
+            .nodes
+            .iter()
+            .map(ReconnectingConnection::reconnections)
+            .collect();
+        let inner = Arc::downgrade(inner);
+        task::spawn(async move {
+            loop {
+                tokio::time::sleep(super::HEARTBEAT_SLEEP_DURATION).await;
+                let Some(inner) = inner.upgrade() else {
+                    // Client was dropped, refreshing can stop.
+                    return;
+                };
+                let failed_over = inner.primary_index() != known_primary_index;
+                known_primary_index = inner.primary_index();
+                for (index, node) in inner.nodes.iter().enumerate() {
+                    let reconnections = node.reconnections();
+                    if !failed_over && reconnections == known_reconnections[index] {
+                        continue;
+                    }
+                    known_reconnections[index] = reconnections;
+                    let availability_zone = get_availability_zone(node).await;
+                    *inner.availability_zones[index].write().unwrap() = availability_zone;
+                }
+            }
+        });
+    }
+
+    /// Follows the primary changes announced by the sentinels, so that writes are sent to the new primary after a
+    /// failover. Only nodes that the client is connected to can become its primary - if a sentinel promotes a node
+    /// that wasn't discovered when the client was created, the client must be recreated.
+    pub(super) fn start_sentinel_failover_watcher(
+        &self,
+        sentinels: Vec<NodeAddress>,
+        tls_mode: TlsMode,
+        master_name: String,
+    ) {
+        const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+        const DROP_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+        let inner = Arc::downgrade(&self.inner);
+        task::spawn(async move {
+            for sentinel in sentinels.iter().cycle() {
+                if inner.strong_count() == 0 {
+                    return;
+                }
+                let (sender, mut receiver) = mpsc::unbounded_channel();
+                // The connection must be kept alive for the pushes to be received.
+                let _connection = match sentinel::subscribe_to_primary_changes(
+                    sentinel, tls_mode, sender,
+                )
+                .await
+                {
+                    Ok(connection) => connection,
+                    Err(err) => {
+                        log_warn(
+                            "sentinel",
+                            format!("Failed to subscribe to sentinel {sentinel}: {err}"),
+                        );
+                        tokio::time::sleep(RECONNECT_DELAY).await;
+                        continue;
+                    }
+                };
+                loop {
+                    let push_info =
+                        match tokio::time::timeout(DROP_CHECK_INTERVAL, receiver.recv()).await {
+                            Ok(Some(push_info)) => push_info,
+                            Ok(None) => break,
+                            Err(_) if inner.strong_count() == 0 => return,
+                            Err(_) => continue,
+                        };
+                    if matches!(push_info.kind, PushKind::Disconnection) {
+                        break;
+                    }
+                    let Some(new_primary) = sentinel::new_primary_address(&push_info, &master_name)
+                    else {
+                        continue;
+                    };
+                    let Some(inner) = inner.upgrade() else {
+                        return;
+                    };
+                    match inner
+                        .nodes
+                        .iter()
+                        .position(|node| node.node_address() == new_primary)
+                    {
+                        Some(index) => inner.primary_index.store(index, Ordering::Relaxed),
+                        None => log_warn(
+                            "sentinel",
+                            format!("New primary `{new_primary}` isn't one of the client's nodes"),
+                        ),
+                    }
+                }
+            }
+        });
+    }
+
     #[cfg(standalone_heartbeat)]
     fn start_heartbeat(reconnecting_connection: ReconnectingConnection) {
         task::spawn(async move {
+            // The heartbeat uses its own management connection, so that it doesn't compete with user requests.
+            let mut management_connection = None;
             loop {
                 tokio::time::sleep(super::HEARTBEAT_SLEEP_DURATION).await;
                 if reconnecting_connection.is_dropped() {
@@ -377,22 +844,32 @@ impl StandaloneClient {
                     return;
                 }
 
-                let Some(mut connection) = reconnecting_connection.try_get_connection().await
-                else {
+                if reconnecting_connection.try_get_connection().await.is_none() {
                     log_debug(
                         "StandaloneClient",
                         "heartbeat stopped while connection is reconnecting",
                     );
                     // Client is reconnecting..
                     continue;
-                };
+                }
                 log_debug("StandaloneClient", "performing heartbeat");
-                if connection
-                    .send_packed_command(&redis::cmd("PING"))
-                    .await
+                let result = match management_connection.as_mut() {
+                    Some(connection) => connection.send_packed_command(&redis::cmd("PING")).await,
+                    None => match reconnecting_connection.create_management_connection().await {
+                        Ok(mut connection) => {
+                            let result = connection.send_packed_command(&redis::cmd("PING")).await;
+                            management_connection = Some(connection);
+                            result
+                        }
+                        Err(err) => Err(err),
+                    },
+                };
+                reconnecting_connection.record_health_check(result.is_ok());
+                if result
                     .is_err_and(|err| err.is_connection_dropped() || err.is_connection_refusal())
                 {
                     log_debug("StandaloneClient", "heartbeat triggered reconnect");
+                    management_connection = None;
                     reconnecting_connection.reconnect();
                 }
             }
@@ -437,12 +914,213 @@ async fn get_connection_and_replication_info(
     }
 }
 
-fn get_read_from(read_from: Option<super::ReadFrom>) -> ReadFrom {
-    match read_from {
-        Some(super::ReadFrom::Primary) => ReadFrom::Primary,
-        Some(super::ReadFrom::PreferReplica) => ReadFrom::PreferReplica {
+async fn create_connection_pool(
+    address: &NodeAddress,
+    size: usize,
+    retry_strategy: &RetryStrategy,
+    connection_info: &redis::RedisConnectionInfo,
+    tls_mode: TlsMode,
+) -> ConnectionPool {
+    let connections = future::join_all((0..size).map(|_| {
+        ReconnectingConnection::new(
+            address,
+            retry_strategy.clone(),
+            connection_info.clone(),
+            tls_mode,
+            None,
+        )
+    }))
+    .await
+    .into_iter()
+    // Connections that failed to connect keep reconnecting in the background.
+    .map(|result| result.unwrap_or_else(|(connection, _)| connection))
+    .collect();
+    ConnectionPool {
+        connections,
+        latest_index: AtomicUsize::new(0),
+    }
+}
+
+/// Returns two distinct random indices in `0..len`. `len` must be at least 2.
+fn two_random_indices(len: usize) -> [usize; 2] {
+    let mut rng = rand::thread_rng();
+    let first = rng.gen_range(0..len);
+    // Choose from the remaining indices, and skip over the first choice.
+    let second = rng.gen_range(0..len - 1);
+    [first, if second >= first { second + 1 } else { second }]
+}
+
+async fn get_replication_offset(node: &ReconnectingConnection) -> Option<u64> {
+    let mut connection = node.try_get_connection().await?;
+    let replication_status = connection
+        .send_packed_command(redis::cmd("INFO").arg("REPLICATION"))
+        .await
+        .ok()?;
+    parse_replication_offset(&redis::from_owned_redis_value::<String>(replication_status).ok()?)
+}
+
+/// Returns the node's availability zone, or `None` if it isn't configured or can't be read. Servers that restrict
+/// `CONFIG`, such as some managed services, are asked with `CLIENT INFO` instead.
+async fn get_availability_zone(node: &ReconnectingConnection) -> Option<String> {
+    let mut connection = node.try_get_connection().await?;
+    let availability_zone = connection
+        .send_packed_command(redis::cmd("CONFIG").arg("GET").arg("availability-zone"))
+        .await
+        .ok()
+        .and_then(parse_availability_zone);
+    if availability_zone.is_some() {
+        return availability_zone;
+    }
+    let client_info = connection
+        .send_packed_command(redis::cmd("CLIENT").arg("INFO"))
+        .await
+        .ok()?;
+    parse_client_info_availability_zone(&redis::from_owned_redis_value::<String>(client_info).ok()?)
+}
+
+/// Extracts the availability zone from the `availability_zone` field of `CLIENT INFO`'s output.
+fn parse_client_info_availability_zone(client_info: &str) -> Option<String> {
+    client_info
+        .split_whitespace()
+        .find_map(|field| field.strip_prefix("availability_zone="))
+        .filter(|availability_zone| !availability_zone.is_empty())
+        .map(str::to_string)
+}
+
+/// Extracts the availability zone from the output of `CONFIG GET availability-zone`, which is a map in RESP3 and an
+/// array of the name and value in RESP2.
+fn parse_availability_zone(value: Value) -> Option<String> {
+    let value = match value {
+        Value::Map(entries) => entries.into_iter().next()?.1,
+        Value::Array(values) => values.into_iter().nth(1)?,
+        _ => return None,
+    };
+    let availability_zone = match value {
+        Value::BulkString(bytes) => String::from_utf8(bytes).ok()?,
+        Value::SimpleString(string) => string,
+        _ => return None,
+    };
+    Some(availability_zone).filter(|availability_zone| !availability_zone.is_empty())
+}
+
+/// Extracts the replication offset of a node from its `INFO REPLICATION` output.
+/// Primaries report their own offset, and replicas report the offset they've processed from their primary.
+fn parse_replication_offset(replication_status: &str) -> Option<u64> {
+    let field = if replication_status.contains("role:master") {
+        "master_repl_offset:"
+    } else {
+        "slave_repl_offset:"
+    };
+    replication_status
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(field))
+        .and_then(|offset| offset.parse().ok())
+}
+
+fn get_replica_selector(
+    replica_selection: Option<ReplicaSelection>,
+    address_indices: &[usize],
+) -> ReplicaSelector {
+    match replica_selection.unwrap_or_default() {
+        ReplicaSelection::RoundRobin => ReplicaSelector::RoundRobin {
             latest_read_replica_index: Default::default(),
         },
-        None => ReadFrom::Primary,
+        ReplicaSelection::Random => ReplicaSelector::Random,
+        ReplicaSelection::LeastOutstandingRequests => ReplicaSelector::LeastOutstandingRequests,
+        ReplicaSelection::PowerOfTwoChoices => ReplicaSelector::PowerOfTwoChoices,
+        ReplicaSelection::Weighted(weights) => ReplicaSelector::Weighted(
+            // Reorder the weights to match the order of the nodes.
+            address_indices
+                .iter()
+                .map(|address_index| weights.get(*address_index).copied().unwrap_or(1))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get_replica_selector, parse_availability_zone, parse_client_info_availability_zone,
+        parse_replication_offset, two_random_indices, ReplicaSelector,
+    };
+    use crate::client::ReplicaSelection;
+    use redis::Value;
+
+    #[test]
+    fn test_weighted_replica_selector_follows_node_order() {
+        let selector =
+            get_replica_selector(Some(ReplicaSelection::Weighted(vec![5, 3])), &[2, 0, 1]);
+        let ReplicaSelector::Weighted(weights) = selector else {
+            panic!("Expected weighted selector, got {selector:?}");
+        };
+        // The third address has no explicit weight, so it defaults to 1.
+        assert_eq!(weights, vec![1, 5, 3]);
+    }
+
+    #[test]
+    fn test_two_random_indices_are_distinct_and_in_range() {
+        for len in 2..10 {
+            for _ in 0..100 {
+                let [first, second] = two_random_indices(len);
+                assert_ne!(first, second);
+                assert!(first < len && second < len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_replication_offset_of_primary() {
+        let info = "# Replication\r\nrole:master\r\nconnected_slaves:1\r\nslave0:ip=127.0.0.1,port=6380,state=online,offset=1200,lag=0\r\nmaster_repl_offset:1234\r\n";
+        assert_eq!(parse_replication_offset(info), Some(1234));
+    }
+
+    #[test]
+    fn test_parse_replication_offset_of_replica() {
+        let info = "# Replication\r\nrole:slave\r\nmaster_host:127.0.0.1\r\nslave_repl_offset:1100\r\nmaster_repl_offset:1100\r\n";
+        assert_eq!(parse_replication_offset(info), Some(1100));
+    }
+
+    #[test]
+    fn test_parse_replication_offset_without_offset() {
+        assert_eq!(
+            parse_replication_offset("# Replication\r\nrole:slave\r\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_availability_zone() {
+        let name = Value::BulkString(b"availability-zone".to_vec());
+        let zone = Value::BulkString(b"us-east-1a".to_vec());
+        assert_eq!(
+            parse_availability_zone(Value::Array(vec![name.clone(), zone.clone()])),
+            Some("us-east-1a".to_string())
+        );
+        assert_eq!(
+            parse_availability_zone(Value::Map(vec![(name.clone(), zone)])),
+            Some("us-east-1a".to_string())
+        );
+        assert_eq!(
+            parse_availability_zone(Value::Array(vec![name, Value::BulkString(Vec::new())])),
+            None
+        );
+        // Servers that don't support the configuration return an empty result.
+        assert_eq!(parse_availability_zone(Value::Array(Vec::new())), None);
+    }
+
+    #[test]
+    fn test_parse_client_info_availability_zone() {
+        assert_eq!(
+            parse_client_info_availability_zone(
+                "id=3 addr=127.0.0.1:6379 name= availability_zone=us-east-1a db=0\n"
+            ),
+            Some("us-east-1a".to_string())
+        );
+        assert_eq!(
+            parse_client_info_availability_zone("id=3 availability_zone= db=0\n"),
+            None
+        );
+        assert_eq!(parse_client_info_availability_zone("id=3 db=0\n"), None);
     }
 }
@@ -0,0 +1,517 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use super::command_keys::first_key;
+use redis::cluster_routing::Routable;
+use redis::{Cmd, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+
+/// Configuration for reporting the keys of requests with large payloads.
+#[derive(Debug, Clone, Copy)]
+pub struct BigKeyDetection {
+    /// Requests whose request or response payload is larger than this value, in bytes, are reported.
+    pub threshold: usize,
+    /// The maximal number of keys kept in the report. Once full, only keys larger than the smallest reported key are added.
+    pub max_keys: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct BigKey {
+    key: Vec<u8>,
+    command: Vec<u8>,
+    request_size: usize,
+    response_size: usize,
+}
+
+impl BigKey {
+    fn size(&self) -> usize {
+        self.request_size.max(self.response_size)
+    }
+}
+
+/// A bounded report of the largest payloads seen by the client.
+pub(super) struct BigKeyTracker {
+    config: BigKeyDetection,
+    keys: Mutex<Vec<BigKey>>,
+}
+
+impl BigKeyTracker {
+    pub(super) fn new(config: BigKeyDetection) -> Self {
+        Self {
+            config,
+            keys: Mutex::new(Vec::with_capacity(config.max_keys)),
+        }
+    }
+
+    /// Records the command's key if its request or response payloads exceed the configured threshold.
+    pub(super) fn record(&self, cmd: &Cmd, response: &Value) {
+        let request_size = cmd_size(cmd);
+        // Calculating the response size walks the whole response, so skip it if the request is already large enough.
+        let response_size = if request_size > self.config.threshold {
+            0
+        } else {
+            value_size(response)
+        };
+        if request_size.max(response_size) <= self.config.threshold {
+            return;
+        }
+        let (Some(command), Some(key)) = (cmd.command(), first_key(cmd)) else {
+            return;
+        };
+        self.insert(BigKey {
+            key: key.to_vec(),
+            command,
+            request_size,
+            response_size,
+        });
+    }
+
+    fn insert(&self, big_key: BigKey) {
+        let mut keys = self.keys.lock().unwrap();
+        if let Some(existing) = keys
+            .iter_mut()
+            .find(|existing| existing.key == big_key.key && existing.command == big_key.command)
+        {
+            if big_key.size() > existing.size() {
+                *existing = big_key;
+            }
+            return;
+        }
+        if keys.len() < self.config.max_keys {
+            keys.push(big_key);
+            return;
+        }
+        let Some((smallest_index, smallest)) = keys
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, existing)| existing.size())
+        else {
+            return;
+        };
+        if smallest.size() < big_key.size() {
+            keys[smallest_index] = big_key;
+        }
+    }
+
+    /// Returns the reported keys, from the largest to the smallest, as an array of maps.
+    pub(super) fn report(&self) -> Value {
+        let mut keys = self.keys.lock().unwrap().clone();
+        keys.sort_by_key(|big_key| std::cmp::Reverse(big_key.size()));
+        Value::Array(
+            keys.into_iter()
+                .map(|big_key| {
+                    Value::Map(vec![
+                        (
+                            Value::SimpleString("key".to_string()),
+                            Value::BulkString(big_key.key),
+                        ),
+                        (
+                            Value::SimpleString("command".to_string()),
+                            Value::BulkString(big_key.command),
+                        ),
+                        (
+                            Value::SimpleString("request_size".to_string()),
+                            Value::Int(big_key.request_size as i64),
+                        ),
+                        (
+                            Value::SimpleString("response_size".to_string()),
+                            Value::Int(big_key.response_size as i64),
+                        ),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Configuration for estimating the most frequently accessed keys.
+#[derive(Debug, Clone, Copy)]
+pub struct HotKeyDetection {
+    /// The number of hottest keys kept in the report.
+    pub top_keys: usize,
+}
+
+const SKETCH_DEPTH: usize = 4;
+const SKETCH_WIDTH: usize = 2048;
+
+/// Estimates key access frequencies with a count-min sketch, and keeps the most frequent keys.
+/// The counts are approximate - a key's count can be overestimated, but is never underestimated.
+pub(super) struct HotKeySketch {
+    config: HotKeyDetection,
+    counters: Vec<AtomicU32>,
+    top_keys: Mutex<Vec<(Vec<u8>, u32)>>,
+    /// The smallest count in the top keys, or 0 while there are fewer top keys than configured. Keys whose estimate
+    /// doesn't exceed it can't enter the top keys, so they're counted without taking the lock.
+    min_top_count: AtomicU32,
+}
+
+impl HotKeySketch {
+    pub(super) fn new(config: HotKeyDetection) -> Self {
+        Self {
+            config,
+            counters: (0..SKETCH_DEPTH * SKETCH_WIDTH)
+                .map(|_| AtomicU32::new(0))
+                .collect(),
+            top_keys: Mutex::new(Vec::with_capacity(config.top_keys)),
+            min_top_count: AtomicU32::new(0),
+        }
+    }
+
+    fn counter_index(row: usize, key: &[u8]) -> usize {
+        let mut hasher = DefaultHasher::new();
+        row.hash(&mut hasher);
+        key.hash(&mut hasher);
+        row * SKETCH_WIDTH + (hasher.finish() as usize % SKETCH_WIDTH)
+    }
+
+    /// Counts an access to the command's key.
+    pub(super) fn record(&self, cmd: &Cmd) {
+        if let Some(key) = first_key(cmd) {
+            self.record_key(key);
+        }
+    }
+
+    fn record_key(&self, key: &[u8]) {
+        let estimate = (0..SKETCH_DEPTH)
+            .map(|row| {
+                self.counters[Self::counter_index(row, key)]
+                    .fetch_add(1, Ordering::Relaxed)
+                    .saturating_add(1)
+            })
+            .min()
+            .unwrap_or_default();
+        // The estimates of the top keys only grow, so a top key's estimate always exceeds the smallest top count.
+        if estimate <= self.min_top_count.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let mut top_keys = self.top_keys.lock().unwrap();
+        if let Some((_, count)) = top_keys
+            .iter_mut()
+            .find(|(top_key, _)| top_key.as_slice() == key)
+        {
+            *count = estimate;
+        } else if top_keys.len() < self.config.top_keys {
+            top_keys.push((key.to_vec(), estimate));
+        } else if let Some((coldest_index, _)) = top_keys
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, count))| *count < estimate)
+            .min_by_key(|(_, (_, count))| *count)
+        {
+            top_keys[coldest_index] = (key.to_vec(), estimate);
+        }
+        let min_top_count = if top_keys.len() < self.config.top_keys {
+            0
+        } else {
+            top_keys
+                .iter()
+                .map(|(_, count)| *count)
+                .min()
+                .unwrap_or(u32::MAX)
+        };
+        self.min_top_count.store(min_top_count, Ordering::Relaxed);
+    }
+
+    /// Returns the hottest keys, from the most to the least frequently accessed, as an array of maps.
+    pub(super) fn report(&self) -> Value {
+        let mut top_keys = self.top_keys.lock().unwrap().clone();
+        top_keys.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        Value::Array(
+            top_keys
+                .into_iter()
+                .map(|(key, count)| {
+                    Value::Map(vec![
+                        (
+                            Value::SimpleString("key".to_string()),
+                            Value::BulkString(key),
+                        ),
+                        (
+                            Value::SimpleString("count".to_string()),
+                            Value::Int(count.into()),
+                        ),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The upper bounds of the payload size histograms' buckets, in bytes. Larger payloads are counted in a last bucket.
+const PAYLOAD_SIZE_BUCKET_BOUNDS: [usize; 8] = [
+    64,
+    256,
+    1024,
+    4 * 1024,
+    16 * 1024,
+    64 * 1024,
+    256 * 1024,
+    1024 * 1024,
+];
+const PAYLOAD_SIZE_BUCKETS: usize = PAYLOAD_SIZE_BUCKET_BOUNDS.len() + 1;
+
+fn payload_size_bucket(size: usize) -> usize {
+    PAYLOAD_SIZE_BUCKET_BOUNDS.partition_point(|bound| *bound < size)
+}
+
+#[derive(Default)]
+struct PayloadSizeHistogram {
+    requests: [AtomicU64; PAYLOAD_SIZE_BUCKETS],
+    responses: [AtomicU64; PAYLOAD_SIZE_BUCKETS],
+}
+
+impl PayloadSizeHistogram {
+    fn record(&self, request_size: usize, response_size: Option<usize>) {
+        self.requests[payload_size_bucket(request_size)].fetch_add(1, Ordering::Relaxed);
+        if let Some(response_size) = response_size {
+            self.responses[payload_size_bucket(response_size)].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Histograms of the request and response payload sizes of each command, used to find the commands whose payloads
+/// cause latency outliers.
+#[derive(Default)]
+pub(super) struct PayloadSizeHistograms {
+    /// The write lock is taken only to add the histogram of a command's first request, and the buckets of existing
+    /// histograms are counted under the read lock.
+    histograms: RwLock<HashMap<Vec<u8>, PayloadSizeHistogram>>,
+}
+
+impl PayloadSizeHistograms {
+    /// Records the request's size, and the response's size if the request succeeded.
+    pub(super) fn record(&self, cmd: &Cmd, response: Option<&Value>) {
+        let Some(command) = cmd.command() else {
+            return;
+        };
+        let request_size = cmd_size(cmd);
+        let response_size = response.map(value_size);
+        if let Some(histogram) = self.histograms.read().unwrap().get(&command) {
+            histogram.record(request_size, response_size);
+            return;
+        }
+        self.histograms
+            .write()
+            .unwrap()
+            .entry(command)
+            .or_default()
+            .record(request_size, response_size);
+    }
+
+    /// Returns the buckets' upper bounds, and a map from each command to the counts of its requests and responses in
+    /// each bucket.
+    pub(super) fn report(&self) -> Value {
+        let counts = |counts: &[AtomicU64]| {
+            Value::Array(
+                counts
+                    .iter()
+                    .map(|count| Value::Int(count.load(Ordering::Relaxed) as i64))
+                    .collect(),
+            )
+        };
+        let histograms = self.histograms.read().unwrap();
+        Value::Map(vec![
+            (
+                Value::SimpleString("bucket_bounds".to_string()),
+                Value::Array(
+                    PAYLOAD_SIZE_BUCKET_BOUNDS
+                        .iter()
+                        .map(|bound| Value::Int(*bound as i64))
+                        .collect(),
+                ),
+            ),
+            (
+                Value::SimpleString("commands".to_string()),
+                Value::Map(
+                    histograms
+                        .iter()
+                        .map(|(command, histogram)| {
+                            (
+                                Value::BulkString(command.clone()),
+                                Value::Map(vec![
+                                    (
+                                        Value::SimpleString("requests".to_string()),
+                                        counts(&histogram.requests),
+                                    ),
+                                    (
+                                        Value::SimpleString("responses".to_string()),
+                                        counts(&histogram.responses),
+                                    ),
+                                ]),
+                            )
+                        })
+                        .collect(),
+                ),
+            ),
+        ])
+    }
+}
+
+fn cmd_size(cmd: &Cmd) -> usize {
+    (0..)
+        .map_while(|index| cmd.arg_idx(index))
+        .map(|arg| arg.len())
+        .sum()
+}
+
+/// Returns the approximate size of the value's payload, in bytes.
+pub(super) fn value_size(value: &Value) -> usize {
+    match value {
+        Value::BulkString(bytes) => bytes.len(),
+        Value::SimpleString(string) => string.len(),
+        Value::VerbatimString { text, .. } => text.len(),
+        Value::Array(values) | Value::Set(values) => values.iter().map(value_size).sum(),
+        Value::Push { data, .. } => data.iter().map(value_size).sum(),
+        Value::Map(entries) => entries
+            .iter()
+            .map(|(key, value)| value_size(key) + value_size(value))
+            .sum(),
+        Value::Attribute { data, .. } => value_size(data),
+        _ => std::mem::size_of::<i64>(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        payload_size_bucket, BigKeyDetection, BigKeyTracker, HotKeyDetection, HotKeySketch,
+        PayloadSizeHistograms,
+    };
+    use redis::{cmd, Value};
+    use std::sync::atomic::Ordering;
+
+    fn reported_keys(tracker: &BigKeyTracker) -> Vec<Vec<u8>> {
+        let Value::Array(report) = tracker.report() else {
+            panic!("Expected an array");
+        };
+        report
+            .into_iter()
+            .map(|entry| {
+                let Value::Map(entries) = entry else {
+                    panic!("Expected a map");
+                };
+                let Value::BulkString(key) = entries[0].1.clone() else {
+                    panic!("Expected a bulk string");
+                };
+                key
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_big_key_tracker_ignores_small_payloads() {
+        let tracker = BigKeyTracker::new(BigKeyDetection {
+            threshold: 10,
+            max_keys: 2,
+        });
+        let mut get = cmd("GET");
+        get.arg("foo");
+        tracker.record(&get, &Value::BulkString(b"bar".to_vec()));
+        assert!(reported_keys(&tracker).is_empty());
+
+        tracker.record(&get, &Value::BulkString(vec![b'a'; 20]));
+        assert_eq!(reported_keys(&tracker), vec![b"foo".to_vec()]);
+    }
+
+    #[test]
+    fn test_big_key_tracker_keeps_largest_keys() {
+        let tracker = BigKeyTracker::new(BigKeyDetection {
+            threshold: 10,
+            max_keys: 2,
+        });
+        for (key, size) in [("small", 20), ("large", 40), ("medium", 30)] {
+            let mut set = cmd("SET");
+            set.arg(key).arg(vec![b'a'; size]);
+            tracker.record(&set, &Value::Okay);
+        }
+        assert_eq!(
+            reported_keys(&tracker),
+            vec![b"large".to_vec(), b"medium".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_hot_key_sketch_reports_most_frequent_keys() {
+        let sketch = HotKeySketch::new(HotKeyDetection { top_keys: 2 });
+        for (key, accesses) in [("warm", 5), ("cold", 1), ("hot", 10)] {
+            for _ in 0..accesses {
+                let mut get = cmd("GET");
+                get.arg(key);
+                sketch.record(&get);
+            }
+        }
+        let Value::Array(report) = sketch.report() else {
+            panic!("Expected an array");
+        };
+        let keys_and_counts: Vec<(Value, Value)> = report
+            .into_iter()
+            .map(|entry| {
+                let Value::Map(entries) = entry else {
+                    panic!("Expected a map");
+                };
+                (entries[0].1.clone(), entries[1].1.clone())
+            })
+            .collect();
+        assert_eq!(
+            keys_and_counts,
+            vec![
+                (Value::BulkString(b"hot".to_vec()), Value::Int(10)),
+                (Value::BulkString(b"warm".to_vec()), Value::Int(5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hot_key_sketch_tracks_smallest_top_count() {
+        let sketch = HotKeySketch::new(HotKeyDetection { top_keys: 2 });
+        sketch.record_key(b"hot");
+        sketch.record_key(b"hot");
+        assert_eq!(sketch.min_top_count.load(Ordering::Relaxed), 0);
+        sketch.record_key(b"warm");
+        assert_eq!(sketch.min_top_count.load(Ordering::Relaxed), 1);
+        // A key whose estimate doesn't exceed the smallest top count is only counted.
+        sketch.record_key(b"cold");
+        assert_eq!(sketch.top_keys.lock().unwrap().len(), 2);
+        sketch.record_key(b"cold");
+        assert_eq!(
+            *sketch.top_keys.lock().unwrap(),
+            vec![(b"hot".to_vec(), 2), (b"cold".to_vec(), 2)]
+        );
+        assert_eq!(sketch.min_top_count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_payload_size_buckets() {
+        assert_eq!(payload_size_bucket(0), 0);
+        assert_eq!(payload_size_bucket(64), 0);
+        assert_eq!(payload_size_bucket(65), 1);
+        assert_eq!(payload_size_bucket(1024 * 1024), 7);
+        assert_eq!(payload_size_bucket(1024 * 1024 + 1), 8);
+    }
+
+    #[test]
+    fn test_payload_size_histograms_count_by_command() {
+        let histograms = PayloadSizeHistograms::default();
+        let mut get = cmd("GET");
+        get.arg("key");
+        histograms.record(&get, Some(&Value::BulkString(vec![b'a'; 100])));
+        histograms.record(&get, None);
+
+        let histograms = histograms.histograms.read().unwrap();
+        let histogram = &histograms[b"GET".as_slice()];
+        assert_eq!(histogram.requests[0].load(Ordering::Relaxed), 2);
+        assert_eq!(histogram.responses[1].load(Ordering::Relaxed), 1);
+        assert_eq!(
+            histogram
+                .responses
+                .iter()
+                .map(|count| count.load(Ordering::Relaxed))
+                .sum::<u64>(),
+            1
+        );
+    }
+}
@@ -0,0 +1,281 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+use redis::{PubSubSubscriptionInfo, PubSubSubscriptionKind, PushInfo, PushKind, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, watch};
+use tokio::task;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SubscriptionState {
+    /// The subscription wasn't confirmed by the server yet.
+    Pending,
+    Subscribed,
+    /// The subscription was lost and is awaiting resubscription.
+    Lost,
+}
+
+#[derive(Debug)]
+struct SubscriptionStatus {
+    state: SubscriptionState,
+    messages_received: u64,
+    resubscriptions: u64,
+}
+
+impl SubscriptionStatus {
+    /// Marks a confirmed subscription as lost. Subscriptions that weren't confirmed yet stay pending.
+    fn lose(&mut self) {
+        if self.state == SubscriptionState::Subscribed {
+            self.state = SubscriptionState::Lost;
+        }
+    }
+}
+
+impl Default for SubscriptionStatus {
+    fn default() -> Self {
+        Self {
+            state: SubscriptionState::Pending,
+            messages_received: 0,
+            resubscriptions: 0,
+        }
+    }
+}
+
+/// Tracks the confirmation state of the configured subscriptions, based on the push notifications received from the server.
+pub(super) struct SubscriptionTracker {
+    states: Mutex<HashMap<(PubSubSubscriptionKind, Vec<u8>), SubscriptionStatus>>,
+    /// Set when all configured subscriptions are confirmed.
+    all_subscribed: watch::Sender<bool>,
+}
+
+impl SubscriptionTracker {
+    pub(super) fn new(subscriptions: &PubSubSubscriptionInfo) -> Self {
+        let states: HashMap<_, _> = subscriptions
+            .iter()
+            .flat_map(|(kind, channels)| {
+                channels
+                    .iter()
+                    .map(|channel| ((*kind, channel.clone()), SubscriptionStatus::default()))
+            })
+            .collect();
+        let (all_subscribed, _) = watch::channel(states.is_empty());
+        Self {
+            states: Mutex::new(states),
+            all_subscribed,
+        }
+    }
+
+    /// Forwards the push notifications to `push_sender`, while updating the subscriptions' state.
+    /// Returns the sender that should be passed to the connections instead of `push_sender`.
+    pub(super) fn intercept(
+        self: &Arc<Self>,
+        push_sender: mpsc::UnboundedSender<PushInfo>,
+    ) -> mpsc::UnboundedSender<PushInfo> {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let tracker = self.clone();
+        // The task completes once the connections drop their senders, or the receiver of the notifications is closed.
+        task::spawn(async move {
+            while let Some(push_info) = receiver.recv().await {
+                tracker.observe(&push_info);
+                if push_sender.send(push_info).is_err() {
+                    return;
+                }
+            }
+        });
+        sender
+    }
+
+    fn observe(&self, push_info: &PushInfo) {
+        let kind = match push_info.kind {
+            PushKind::Subscribe | PushKind::Unsubscribe | PushKind::Message => {
+                PubSubSubscriptionKind::Exact
+            }
+            PushKind::PSubscribe | PushKind::PUnsubscribe | PushKind::PMessage => {
+                PubSubSubscriptionKind::Pattern
+            }
+            PushKind::SSubscribe | PushKind::SUnsubscribe | PushKind::SMessage => {
+                PubSubSubscriptionKind::Sharded
+            }
+            PushKind::Disconnection => {
+                let mut states = self.states.lock().unwrap();
+                states.values_mut().for_each(|status| status.lose());
+                self.all_subscribed.send_replace(states.is_empty());
+                return;
+            }
+            _ => return,
+        };
+        // Messages received through a pattern subscription start with the pattern, so all of the notifications
+        // start with the configured channel or pattern.
+        let Some(Value::BulkString(channel)) = push_info.data.first() else {
+            return;
+        };
+
+        let mut states = self.states.lock().unwrap();
+        // Only configured subscriptions are tracked.
+        let Some(status) = states.get_mut(&(kind, channel.clone())) else {
+            return;
+        };
+        match push_info.kind {
+            PushKind::Message | PushKind::PMessage | PushKind::SMessage => {
+                status.messages_received += 1;
+                return;
+            }
+            PushKind::Subscribe | PushKind::PSubscribe | PushKind::SSubscribe => {
+                if status.state == SubscriptionState::Lost {
+                    status.resubscriptions += 1;
+                }
+                status.state = SubscriptionState::Subscribed;
+            }
+            // The configured subscriptions are resubscribed by the connections.
+            _ => status.lose(),
+        }
+        self.all_subscribed.send_replace(
+            states
+                .values()
+                .all(|status| status.state == SubscriptionState::Subscribed),
+        );
+    }
+
+    /// Completes once all configured subscriptions are confirmed by the server.
+    pub(super) async fn wait_for_subscriptions(&self) {
+        let mut receiver = self.all_subscribed.subscribe();
+        while !*receiver.borrow_and_update() {
+            // The sender is owned by the tracker, so it can't be closed while waiting.
+            if receiver.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Returns the state of each configured subscription, along with the number of messages received through it and
+    /// the number of times it was resubscribed after being lost, as an array of maps.
+    pub(super) fn report(&self) -> Value {
+        let states = self.states.lock().unwrap();
+        Value::Array(
+            states
+                .iter()
+                .map(|((kind, channel), status)| {
+                    let kind = match kind {
+                        PubSubSubscriptionKind::Exact => "exact",
+                        PubSubSubscriptionKind::Pattern => "pattern",
+                        PubSubSubscriptionKind::Sharded => "sharded",
+                    };
+                    let state = match status.state {
+                        SubscriptionState::Pending => "pending",
+                        SubscriptionState::Subscribed => "subscribed",
+                        SubscriptionState::Lost => "lost",
+                    };
+                    Value::Map(vec![
+                        (
+                            Value::SimpleString("kind".to_string()),
+                            Value::SimpleString(kind.to_string()),
+                        ),
+                        (
+                            Value::SimpleString("channel".to_string()),
+                            Value::BulkString(channel.clone()),
+                        ),
+                        (
+                            Value::SimpleString("state".to_string()),
+                            Value::SimpleString(state.to_string()),
+                        ),
+                        (
+                            Value::SimpleString("messages_received".to_string()),
+                            Value::Int(status.messages_received as i64),
+                        ),
+                        (
+                            Value::SimpleString("resubscriptions".to_string()),
+                            Value::Int(status.resubscriptions as i64),
+                        ),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubscriptionTracker;
+    use redis::{PubSubSubscriptionInfo, PubSubSubscriptionKind, PushInfo, PushKind, Value};
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    fn push(kind: PushKind, channel: &str) -> PushInfo {
+        PushInfo {
+            kind,
+            data: vec![
+                Value::BulkString(channel.as_bytes().to_vec()),
+                Value::Int(1),
+            ],
+        }
+    }
+
+    fn tracker() -> SubscriptionTracker {
+        let mut subscriptions = PubSubSubscriptionInfo::new();
+        subscriptions.insert(
+            PubSubSubscriptionKind::Exact,
+            HashSet::from([b"channel".to_vec()]),
+        );
+        subscriptions.insert(
+            PubSubSubscriptionKind::Pattern,
+            HashSet::from([b"pattern*".to_vec()]),
+        );
+        SubscriptionTracker::new(&subscriptions)
+    }
+
+    async fn is_subscribed(tracker: &SubscriptionTracker) -> bool {
+        tokio::time::timeout(Duration::from_millis(10), tracker.wait_for_subscriptions())
+            .await
+            .is_ok()
+    }
+
+    #[tokio::test]
+    async fn test_waits_until_all_subscriptions_are_confirmed() {
+        let tracker = tracker();
+        assert!(!is_subscribed(&tracker).await);
+
+        tracker.observe(&push(PushKind::Subscribe, "channel"));
+        // A pattern subscription doesn't confirm an exact subscription with the same name.
+        tracker.observe(&push(PushKind::Subscribe, "pattern*"));
+        assert!(!is_subscribed(&tracker).await);
+
+        tracker.observe(&push(PushKind::PSubscribe, "pattern*"));
+        assert!(is_subscribed(&tracker).await);
+    }
+
+    #[tokio::test]
+    async fn test_disconnection_requires_resubscription() {
+        let tracker = tracker();
+        tracker.observe(&push(PushKind::Subscribe, "channel"));
+        tracker.observe(&push(PushKind::PSubscribe, "pattern*"));
+        assert!(is_subscribed(&tracker).await);
+
+        tracker.observe(&PushInfo {
+            kind: PushKind::Disconnection,
+            data: vec![],
+        });
+        assert!(!is_subscribed(&tracker).await);
+
+        tracker.observe(&push(PushKind::Subscribe, "channel"));
+        tracker.observe(&push(PushKind::PSubscribe, "pattern*"));
+        assert!(is_subscribed(&tracker).await);
+    }
+
+    #[test]
+    fn test_counts_messages_and_resubscriptions() {
+        let tracker = tracker();
+        tracker.observe(&push(PushKind::Subscribe, "channel"));
+        tracker.observe(&push(PushKind::Message, "channel"));
+        tracker.observe(&push(PushKind::Unsubscribe, "channel"));
+        tracker.observe(&push(PushKind::Subscribe, "channel"));
+        tracker.observe(&push(PushKind::Message, "channel"));
+        // Messages of channels that weren't configured aren't counted.
+        tracker.observe(&push(PushKind::Message, "other"));
+
+        let states = tracker.states.lock().unwrap();
+        let status = &states[&(PubSubSubscriptionKind::Exact, b"channel".to_vec())];
+        assert_eq!(status.messages_received, 2);
+        assert_eq!(status.resubscriptions, 1);
+    }
+}
@@ -2,6 +2,8 @@
  * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
  */
 
+use super::concurrency_limits::CommandConcurrencyLimit;
+use super::statistics::{BigKeyDetection, HotKeyDetection};
 use logger_core::log_warn;
 use std::collections::HashSet;
 use std::time::Duration;
@@ -23,6 +25,28 @@ pub struct ConnectionRequest {
     pub connection_retry_strategy: Option<ConnectionRetryStrategy>,
     pub periodic_checks: Option<PeriodicCheck>,
     pub pubsub_subscriptions: Option<redis::PubSubSubscriptionInfo>,
+    pub replica_lag_check: Option<ReplicaLagCheck>,
+    pub replica_selection: Option<ReplicaSelection>,
+    pub big_key_detection: Option<BigKeyDetection>,
+    pub hot_key_detection: Option<HotKeyDetection>,
+    pub command_concurrency_limits: Vec<CommandConcurrencyLimit>,
+    /// Added to the timeout of blocking commands to get the request's timeout.
+    pub blocking_command_timeout_extension: Option<Duration>,
+    /// Connect only to the node holding the pubsub subscriptions, for clients that only consume messages.
+    pub subscriber_only: bool,
+    /// Record histograms of the request and response payload sizes of each command.
+    pub payload_size_histograms: bool,
+    /// When set, `addresses` are Sentinel nodes, which are queried for the primary and replicas of this master name.
+    pub sentinel_master_name: Option<String>,
+    /// Fills the options that aren't set in the request with the profile's presets.
+    pub configuration_profile: Option<ConfigurationProfile>,
+    /// The number of multiplexed connections to each node, to avoid head-of-line blocking on a single connection.
+    /// 0 and 1 both mean a single connection.
+    pub connections_per_node: u32,
+    pub connection_pool_dispatch: ConnectionPoolDispatch,
+    /// Commands routed to slots owned by failed primaries fail immediately, instead of being retried until the cluster
+    /// recovers or the request times out.
+    pub fail_fast_on_unavailable_slots: bool,
 }
 
 pub struct AuthenticationInfo {
@@ -38,6 +62,14 @@ pub enum PeriodicCheck {
     ManualInterval(Duration),
 }
 
+/// Periodic sampling of the replication offsets of the replicas, used to estimate how far behind the primary they are.
+#[derive(Debug)]
+pub struct ReplicaLagCheck {
+    pub interval: Duration,
+    /// Replicas lagging by more than this many bytes won't be used for reads.
+    pub max_lag: Option<u64>,
+}
+
 #[derive(Debug)]
 pub struct NodeAddress {
     pub host: String,
@@ -50,11 +82,37 @@ impl ::std::fmt::Display for NodeAddress {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Default)]
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
 pub enum ReadFrom {
     #[default]
     Primary,
     PreferReplica,
+    /// Prefer replicas in the given availability zone, then other replicas, and then the primary.
+    AZAffinity(String),
+}
+
+/// The algorithm used to choose a replica when reading from replicas.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub enum ReplicaSelection {
+    #[default]
+    RoundRobin,
+    Random,
+    /// Choose the replica with the least requests awaiting a response.
+    LeastOutstandingRequests,
+    /// Choose a random replica, with probability proportional to its weight.
+    /// The weights are given in the same order as the addresses in the connection request.
+    Weighted(Vec<u32>),
+    /// Choose two random replicas, and use the one with less requests awaiting a response.
+    PowerOfTwoChoices,
+}
+
+/// How requests are dispatched between the connections of a node.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum ConnectionPoolDispatch {
+    #[default]
+    RoundRobin,
+    /// Choose the connection with the least requests awaiting a response.
+    LeastInflight,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Default)]
@@ -65,6 +123,17 @@ pub enum TlsMode {
     SecureTls,
 }
 
+/// Presets of the timeouts and reconnection options for common deployments.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ConfigurationProfile {
+    /// A cluster mode ElastiCache deployment with in-transit encryption.
+    AwsElastiCacheClusterTls,
+    /// Nodes on the same local network, where requests are expected to complete within a few milliseconds.
+    LowLatencyLan,
+    /// Large batches and pipelines, which may take a long time to complete.
+    HighThroughputBatch,
+}
+
 pub struct ConnectionRetryStrategy {
     pub exponent_base: u32,
     pub factor: u32,
@@ -96,7 +165,7 @@ impl From<protobuf::ConnectionRequest> for ConnectionRequest {
             protobuf::ReadFrom::Primary => ReadFrom::Primary,
             protobuf::ReadFrom::PreferReplica => ReadFrom::PreferReplica,
             protobuf::ReadFrom::LowestLatency => todo!(),
-            protobuf::ReadFrom::AZAffinity => todo!(),
+            protobuf::ReadFrom::AZAffinity => ReadFrom::AZAffinity(value.client_az.to_string()),
         });
 
         let client_name = chars_to_string_option(&value.client_name);
@@ -116,11 +185,34 @@ impl From<protobuf::ConnectionRequest> for ConnectionRequest {
             protobuf::ProtocolVersion::RESP2 => redis::ProtocolVersion::RESP2,
         });
 
-        let tls_mode = value.tls_mode.enum_value().ok().map(|val| match val {
-            protobuf::TlsMode::NoTls => TlsMode::NoTls,
-            protobuf::TlsMode::SecureTls => TlsMode::SecureTls,
-            protobuf::TlsMode::InsecureTls => TlsMode::InsecureTls,
-        });
+        let configuration_profile = value
+            .configuration_profile
+            .enum_value()
+            .ok()
+            .and_then(|val| match val {
+                protobuf::ConfigurationProfile::NoProfile => None,
+                protobuf::ConfigurationProfile::AwsElastiCacheClusterTls => {
+                    Some(ConfigurationProfile::AwsElastiCacheClusterTls)
+                }
+                protobuf::ConfigurationProfile::LowLatencyLan => {
+                    Some(ConfigurationProfile::LowLatencyLan)
+                }
+                protobuf::ConfigurationProfile::HighThroughputBatch => {
+                    Some(ConfigurationProfile::HighThroughputBatch)
+                }
+            });
+
+        let tls_mode = value
+            .tls_mode
+            .enum_value()
+            .ok()
+            .map(|val| match val {
+                protobuf::TlsMode::NoTls => TlsMode::NoTls,
+                protobuf::TlsMode::SecureTls => TlsMode::SecureTls,
+                protobuf::TlsMode::InsecureTls => TlsMode::InsecureTls,
+            })
+            // The default TLS mode can't be told apart from an unset one, so the profile's TLS mode is used.
+            .filter(|tls_mode| configuration_profile.is_none() || *tls_mode != TlsMode::NoTls);
 
         let addresses = value
             .addresses
@@ -187,6 +279,101 @@ impl From<protobuf::ConnectionRequest> for ConnectionRequest {
             pubsub_subscriptions = Some(redis_pubsub);
         }
 
+        let replica_lag_check =
+            value
+                .replica_lag_check
+                .0
+                .map(|replica_lag_check| ReplicaLagCheck {
+                    interval: if replica_lag_check.interval_in_sec == 0 {
+                        super::DEFAULT_REPLICA_LAG_CHECK_INTERVAL
+                    } else {
+                        Duration::from_secs(replica_lag_check.interval_in_sec.into())
+                    },
+                    max_lag: if replica_lag_check.max_lag_in_bytes == 0 {
+                        None
+                    } else {
+                        Some(replica_lag_check.max_lag_in_bytes)
+                    },
+                });
+
+        let replica_selection = value
+            .replica_selection
+            .enum_value()
+            .ok()
+            .map(|val| match val {
+                protobuf::ReplicaSelectionStrategy::RoundRobin => ReplicaSelection::RoundRobin,
+                protobuf::ReplicaSelectionStrategy::Random => ReplicaSelection::Random,
+                protobuf::ReplicaSelectionStrategy::LeastOutstandingRequests => {
+                    ReplicaSelection::LeastOutstandingRequests
+                }
+                protobuf::ReplicaSelectionStrategy::Weighted => {
+                    ReplicaSelection::Weighted(value.node_weights.clone())
+                }
+                protobuf::ReplicaSelectionStrategy::PowerOfTwoChoices => {
+                    ReplicaSelection::PowerOfTwoChoices
+                }
+            });
+
+        let big_key_detection =
+            value
+                .big_key_detection
+                .0
+                .map(|big_key_detection| BigKeyDetection {
+                    threshold: big_key_detection.threshold_in_bytes as usize,
+                    max_keys: if big_key_detection.max_keys == 0 {
+                        super::DEFAULT_BIG_KEYS_REPORT_SIZE
+                    } else {
+                        big_key_detection.max_keys as usize
+                    },
+                });
+
+        let hot_key_detection =
+            value
+                .hot_key_detection
+                .0
+                .map(|hot_key_detection| HotKeyDetection {
+                    top_keys: if hot_key_detection.top_keys == 0 {
+                        super::DEFAULT_HOT_KEYS_REPORT_SIZE
+                    } else {
+                        hot_key_detection.top_keys as usize
+                    },
+                });
+
+        let command_concurrency_limits = value
+            .command_concurrency_limits
+            .iter()
+            .map(|limit| CommandConcurrencyLimit {
+                commands: limit
+                    .commands
+                    .iter()
+                    .map(|command| command.to_string())
+                    .collect(),
+                max_concurrent_requests: limit.max_concurrent_requests,
+            })
+            .collect();
+
+        let blocking_command_timeout_extension =
+            none_if_zero(value.blocking_command_timeout_extension_in_ms)
+                .map(|extension| Duration::from_millis(extension.into()));
+
+        let subscriber_only = value.subscriber_only;
+        let payload_size_histograms = value.payload_size_histograms;
+        let sentinel_master_name = chars_to_string_option(&value.sentinel_master_name);
+        let connections_per_node = value.connections_per_node;
+        let fail_fast_on_unavailable_slots = value.fail_fast_on_unavailable_slots;
+        let connection_pool_dispatch = value
+            .connection_pool_dispatch
+            .enum_value()
+            .map(|val| match val {
+                protobuf::ConnectionPoolDispatch::RoundRobinDispatch => {
+                    ConnectionPoolDispatch::RoundRobin
+                }
+                protobuf::ConnectionPoolDispatch::LeastInflightDispatch => {
+                    ConnectionPoolDispatch::LeastInflight
+                }
+            })
+            .unwrap_or_default();
+
         ConnectionRequest {
             read_from,
             client_name,
@@ -200,6 +387,19 @@ impl From<protobuf::ConnectionRequest> for ConnectionRequest {
             connection_retry_strategy,
             periodic_checks,
             pubsub_subscriptions,
+            replica_lag_check,
+            replica_selection,
+            big_key_detection,
+            hot_key_detection,
+            command_concurrency_limits,
+            blocking_command_timeout_extension,
+            subscriber_only,
+            payload_size_histograms,
+            sentinel_master_name,
+            configuration_profile,
+            connections_per_node,
+            connection_pool_dispatch,
+            fail_fast_on_unavailable_slots,
         }
     }
 }
@@ -37,6 +37,12 @@ pub(crate) enum ExpectedReturnType<'a> {
     XAutoClaimReturnType,
 }
 
+/// Converts the command's response to its expected type, as the client does. Exposed for the fuzz targets.
+#[cfg(fuzzing)]
+pub fn convert_response(cmd: &Cmd, value: Value) -> RedisResult<Value> {
+    convert_to_expected_type(value, expected_type_for_cmd(cmd))
+}
+
 pub(crate) fn convert_to_expected_type(
     value: Value,
     expected: Option<ExpectedReturnType>,
@@ -295,18 +301,21 @@ pub(crate) fn convert_to_expected_type(
                 ),
                 // RESP 2 response
                 Value::BulkString(bytes) => {
-                    let text = std::str::from_utf8(&bytes).unwrap();
-                    let res = convert_lolwut_string(text);
-                    Ok(Value::BulkString(Vec::from(res)))
-                }
-                // RESP 3 response
-                Value::VerbatimString {
-                    format: _,
-                    ref text,
-                } => {
+                    let Ok(text) = std::str::from_utf8(&bytes) else {
+                        return Err((
+                            ErrorKind::TypeError,
+                            "LOLWUT response isn't a valid UTF-8 string",
+                        )
+                            .into());
+                    };
                     let res = convert_lolwut_string(text);
                     Ok(Value::BulkString(Vec::from(res)))
                 }
+                // RESP 3 response - the format marker is kept, so that bindings can preserve it
+                Value::VerbatimString { format, ref text } => Ok(Value::VerbatimString {
+                    format,
+                    text: convert_lolwut_string(text),
+                }),
                 _ => Err((
                     ErrorKind::TypeError,
                     "LOLWUT response couldn't be converted to a user-friendly format",
@@ -568,8 +577,10 @@ pub(crate) fn convert_to_expected_type(
         ExpectedReturnType::FunctionStatsReturnType => match value {
             // TODO reuse https://github.com/Bit-Quill/glide-for-redis/pull/331 and https://github.com/aws/glide-for-redis/pull/1489
             Value::Map(map) => {
-                if map[0].0 == Value::BulkString(b"running_script".into()) {
-                    // already a RESP3 response - do nothing
+                if map.first().map_or(true, |(key, _)| {
+                    *key == Value::BulkString(b"running_script".into())
+                }) {
+                    // already a RESP3 response, or an empty response - do nothing
                     Ok(Value::Map(map))
                 } else {
                     // cluster (multi-node) response - go recursive
@@ -718,9 +729,9 @@ fn convert_array_elements(
     element_type: ExpectedReturnType,
 ) -> RedisResult<Value> {
     let converted_array = array
-        .iter()
-        .map(|v| convert_to_expected_type(v.clone(), Some(element_type)).unwrap())
-        .collect();
+        .into_iter()
+        .map(|v| convert_to_expected_type(v, Some(element_type)))
+        .collect::<RedisResult<_>>()?;
     Ok(Value::Array(converted_array))
 }
 
@@ -1234,7 +1245,10 @@ mod tests {
             Some(ExpectedReturnType::Lolwut),
         );
         assert_eq!(
-            Value::BulkString(expected.clone().into_bytes()),
+            Value::VerbatimString {
+                format: redis::VerbatimFormat::Text,
+                text: expected.clone(),
+            },
             converted_2.unwrap()
         );
 
@@ -2657,4 +2671,81 @@ mod tests {
             })
         ));
     }
+
+    mod proptests {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn arbitrary_value() -> impl Strategy<Value = Value> {
+            let leaf = prop_oneof![
+                Just(Value::Nil),
+                Just(Value::Okay),
+                any::<i64>().prop_map(Value::Int),
+                any::<f64>().prop_map(Value::Double),
+                any::<bool>().prop_map(Value::Boolean),
+                any::<Vec<u8>>().prop_map(Value::BulkString),
+                ".*".prop_map(Value::SimpleString),
+            ];
+            leaf.prop_recursive(4, 64, 8, |inner| {
+                prop_oneof![
+                    prop::collection::vec(inner.clone(), 0..8).prop_map(Value::Array),
+                    prop::collection::vec(inner.clone(), 0..8).prop_map(Value::Set),
+                    prop::collection::vec((inner.clone(), inner), 0..8).prop_map(Value::Map),
+                ]
+            })
+        }
+
+        /// Commands whose responses are converted, along with the arguments that affect the conversion.
+        fn converted_command() -> impl Strategy<Value = Cmd> {
+            prop::sample::select(vec![
+                vec!["HGETALL"],
+                vec!["XCLAIM"],
+                vec!["XCLAIM", "JUSTID"],
+                vec!["XAUTOCLAIM"],
+                vec!["XRANGE"],
+                vec!["XREAD"],
+                vec!["LCS", "IDX"],
+                vec!["INCRBYFLOAT"],
+                vec!["HEXISTS"],
+                vec!["SMISMEMBER"],
+                vec!["SMEMBERS"],
+                vec!["ZSCORE"],
+                vec!["ZMSCORE"],
+                vec!["ZPOPMIN"],
+                vec!["BZPOPMIN"],
+                vec!["BZMPOP"],
+                vec!["JSON.TOGGLE"],
+                vec!["GEOPOS"],
+                vec!["GEOSEARCH", "WITHDIST", "WITHHASH", "WITHCOORD"],
+                vec!["LMPOP"],
+                vec!["HRANDFIELD", "WITHVALUES"],
+                vec!["ZRANDMEMBER", "WITHSCORES"],
+                vec!["ZADD", "INCR"],
+                vec!["ZRANGE", "WITHSCORES"],
+                vec!["ZRANK", "WITHSCORE"],
+                vec!["SPOP"],
+                vec!["LOLWUT"],
+                vec!["FUNCTION", "LIST"],
+                vec!["FUNCTION", "STATS"],
+            ])
+            .prop_map(|args| {
+                let mut cmd = redis::cmd(args[0]);
+                for arg in &args[1..] {
+                    cmd.arg(*arg);
+                }
+                cmd
+            })
+        }
+
+        proptest! {
+            // Malformed responses must be reported as errors, instead of panicking the client.
+            #[test]
+            fn convert_to_expected_type_doesnt_panic(
+                cmd in converted_command(),
+                value in arbitrary_value(),
+            ) {
+                let _ = convert_to_expected_type(value, expected_type_for_cmd(&cmd));
+            }
+        }
+    }
 }
@@ -0,0 +1,186 @@
+/**
+ * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
+ */
+// Parsing of command lines, following the quoting rules of valkey-cli.
+use redis::Cmd;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CommandLineError {
+    #[error("The command line is empty")]
+    Empty,
+    #[error("Unbalanced quotes in the command line")]
+    UnbalancedQuotes,
+    #[error("A closing quote must be followed by a space, or end the command line")]
+    MissingSpaceAfterQuote,
+}
+
+fn hex_digit_value(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|digit| digit as u8)
+}
+
+/// Splits the command line into arguments.
+/// Outside of quotes, arguments are separated by whitespace and taken as is. Inside double quotes, `\n`, `\r`, `\t`,
+/// `\b`, `\a`, `\"`, `\\` and `\xHH` escape sequences are supported. Inside single quotes, only `\'` is supported.
+pub fn split_command_line(line: &str) -> Result<Vec<Vec<u8>>, CommandLineError> {
+    let bytes = line.as_bytes();
+    let mut args = Vec::new();
+    let mut index = 0;
+    loop {
+        while bytes.get(index).is_some_and(u8::is_ascii_whitespace) {
+            index += 1;
+        }
+        if index == bytes.len() {
+            return Ok(args);
+        }
+
+        let mut arg = Vec::new();
+        let mut in_double_quotes = false;
+        let mut in_single_quotes = false;
+        loop {
+            let Some(&byte) = bytes.get(index) else {
+                if in_double_quotes || in_single_quotes {
+                    return Err(CommandLineError::UnbalancedQuotes);
+                }
+                break;
+            };
+            if in_double_quotes {
+                match (byte, bytes.get(index + 1).copied()) {
+                    (b'\\', Some(b'x')) => {
+                        let high = bytes.get(index + 2).copied().and_then(hex_digit_value);
+                        let low = bytes.get(index + 3).copied().and_then(hex_digit_value);
+                        if let (Some(high), Some(low)) = (high, low) {
+                            arg.push(high * 16 + low);
+                            index += 3;
+                        } else {
+                            arg.push(b'x');
+                            index += 1;
+                        }
+                    }
+                    (b'\\', Some(escaped)) => {
+                        arg.push(match escaped {
+                            b'n' => b'\n',
+                            b'r' => b'\r',
+                            b't' => b'\t',
+                            b'b' => 0x08,
+                            b'a' => 0x07,
+                            other => other,
+                        });
+                        index += 1;
+                    }
+                    (b'"', next) => {
+                        if next.is_some_and(|next| !next.is_ascii_whitespace()) {
+                            return Err(CommandLineError::MissingSpaceAfterQuote);
+                        }
+                        in_double_quotes = false;
+                    }
+                    _ => arg.push(byte),
+                }
+            } else if in_single_quotes {
+                match (byte, bytes.get(index + 1).copied()) {
+                    (b'\\', Some(b'\'')) => {
+                        arg.push(b'\'');
+                        index += 1;
+                    }
+                    (b'\'', next) => {
+                        if next.is_some_and(|next| !next.is_ascii_whitespace()) {
+                            return Err(CommandLineError::MissingSpaceAfterQuote);
+                        }
+                        in_single_quotes = false;
+                    }
+                    _ => arg.push(byte),
+                }
+            } else {
+                match byte {
+                    byte if byte.is_ascii_whitespace() => break,
+                    b'"' => in_double_quotes = true,
+                    b'\'' => in_single_quotes = true,
+                    _ => arg.push(byte),
+                }
+            }
+            index += 1;
+        }
+        args.push(arg);
+    }
+}
+
+/// Parses the command line into a command.
+pub fn parse_command_line(line: &str) -> Result<Cmd, CommandLineError> {
+    let args = split_command_line(line)?;
+    if args.is_empty() {
+        return Err(CommandLineError::Empty);
+    }
+    let mut cmd = Cmd::new();
+    for arg in args {
+        cmd.arg(arg);
+    }
+    Ok(cmd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_command_line, split_command_line, CommandLineError};
+
+    fn split(line: &str) -> Vec<Vec<u8>> {
+        split_command_line(line).unwrap()
+    }
+
+    #[test]
+    fn test_split_unquoted_arguments() {
+        assert_eq!(
+            split("  SET\tkey  value "),
+            vec![b"SET".to_vec(), b"key".to_vec(), b"value".to_vec()]
+        );
+        assert_eq!(split("   "), Vec::<Vec<u8>>::new());
+    }
+
+    #[test]
+    fn test_split_double_quoted_arguments() {
+        assert_eq!(
+            split(r#"SET "my key" "a\tb\"c\\d\x41\xZZ""#),
+            vec![
+                b"SET".to_vec(),
+                b"my key".to_vec(),
+                b"a\tb\"c\\dAxZZ".to_vec()
+            ]
+        );
+        assert_eq!(split(r#""""#), vec![b"".to_vec()]);
+    }
+
+    #[test]
+    fn test_split_single_quoted_arguments() {
+        assert_eq!(
+            split(r#"SET 'it\'s' 'a\nb'"#),
+            vec![b"SET".to_vec(), b"it's".to_vec(), b"a\\nb".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_quotes_can_start_inside_an_argument() {
+        assert_eq!(split(r#"key"with space""#), vec![b"keywith space".to_vec()]);
+    }
+
+    #[test]
+    fn test_invalid_quotes() {
+        assert_eq!(
+            split_command_line(r#"SET "key"#),
+            Err(CommandLineError::UnbalancedQuotes)
+        );
+        assert_eq!(
+            split_command_line(r#"SET 'key"#),
+            Err(CommandLineError::UnbalancedQuotes)
+        );
+        assert_eq!(
+            split_command_line(r#"SET "key"value"#),
+            Err(CommandLineError::MissingSpaceAfterQuote)
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_command_line() {
+        assert!(matches!(
+            parse_command_line(" "),
+            Err(CommandLineError::Empty)
+        ));
+    }
+}
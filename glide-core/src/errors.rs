@@ -4,12 +4,17 @@
 
 use redis::RedisError;
 
+/// The code of the errors returned by the client, without sending the request, when the request's slot is owned by a
+/// failed primary. Errors returned by the server, such as `CLUSTERDOWN`, are reported with their own code.
+pub(crate) const SLOT_UNAVAILABLE_ERROR_CODE: &str = "SLOTUNAVAILABLE";
+
 #[repr(C)]
 pub enum RequestErrorType {
     Unspecified = 0,
     ExecAbort = 1,
     Timeout = 2,
     Disconnect = 3,
+    SlotUnavailable = 4,
 }
 
 pub fn error_type(error: &RedisError) -> RequestErrorType {
@@ -19,6 +24,8 @@ pub fn error_type(error: &RedisError) -> RequestErrorType {
         RequestErrorType::Disconnect
     } else if matches!(error.kind(), redis::ErrorKind::ExecAbortError) {
         RequestErrorType::ExecAbort
+    } else if error.code() == Some(SLOT_UNAVAILABLE_ERROR_CODE) {
+        RequestErrorType::SlotUnavailable
     } else {
         RequestErrorType::Unspecified
     }
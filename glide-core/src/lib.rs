@@ -14,5 +14,6 @@ mod socket_listener;
 pub use socket_listener::*;
 pub mod errors;
 pub mod scripts_container;
-pub use client::ConnectionRequest;
+pub use client::{Client, ClientBuilder, ConnectionRequest};
+pub mod command_line;
 pub mod request_type;
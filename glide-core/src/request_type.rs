@@ -214,6 +214,15 @@ pub enum RequestType {
     XAutoClaim = 203,
     Wait = 208,
     XClaim = 209,
+    HExpire = 211,
+    HExpireAt = 212,
+    HPExpire = 213,
+    HPExpireAt = 214,
+    HPersist = 215,
+    HTtl = 216,
+    HPTtl = 217,
+    HExpireTime = 218,
+    HPExpireTime = 219,
 }
 
 fn get_two_word_command(first: &str, second: &str) -> Cmd {
@@ -431,6 +440,15 @@ impl From<::protobuf::EnumOrUnknown<ProtobufRequestType>> for RequestType {
             ProtobufRequestType::XAutoClaim => RequestType::XAutoClaim,
             ProtobufRequestType::Wait => RequestType::Wait,
             ProtobufRequestType::XClaim => RequestType::XClaim,
+            ProtobufRequestType::HExpire => RequestType::HExpire,
+            ProtobufRequestType::HExpireAt => RequestType::HExpireAt,
+            ProtobufRequestType::HPExpire => RequestType::HPExpire,
+            ProtobufRequestType::HPExpireAt => RequestType::HPExpireAt,
+            ProtobufRequestType::HPersist => RequestType::HPersist,
+            ProtobufRequestType::HTtl => RequestType::HTtl,
+            ProtobufRequestType::HPTtl => RequestType::HPTtl,
+            ProtobufRequestType::HExpireTime => RequestType::HExpireTime,
+            ProtobufRequestType::HPExpireTime => RequestType::HPExpireTime,
         }
     }
 }
@@ -646,6 +664,41 @@ impl RequestType {
             RequestType::XAutoClaim => Some(cmd("XAUTOCLAIM")),
             RequestType::Wait => Some(cmd("WAIT")),
             RequestType::XClaim => Some(cmd("XCLAIM")),
+            RequestType::HExpire => Some(cmd("HEXPIRE")),
+            RequestType::HExpireAt => Some(cmd("HEXPIREAT")),
+            RequestType::HPExpire => Some(cmd("HPEXPIRE")),
+            RequestType::HPExpireAt => Some(cmd("HPEXPIREAT")),
+            RequestType::HPersist => Some(cmd("HPERSIST")),
+            RequestType::HTtl => Some(cmd("HTTL")),
+            RequestType::HPTtl => Some(cmd("HPTTL")),
+            RequestType::HExpireTime => Some(cmd("HEXPIRETIME")),
+            RequestType::HPExpireTime => Some(cmd("HPEXPIRETIME")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProtobufRequestType, RequestType};
+    use protobuf::EnumOrUnknown;
+
+    #[test]
+    fn test_hash_field_expiration_request_types() {
+        let request_types = [
+            (ProtobufRequestType::HExpire, "HEXPIRE"),
+            (ProtobufRequestType::HExpireAt, "HEXPIREAT"),
+            (ProtobufRequestType::HPExpire, "HPEXPIRE"),
+            (ProtobufRequestType::HPExpireAt, "HPEXPIREAT"),
+            (ProtobufRequestType::HPersist, "HPERSIST"),
+            (ProtobufRequestType::HTtl, "HTTL"),
+            (ProtobufRequestType::HPTtl, "HPTTL"),
+            (ProtobufRequestType::HExpireTime, "HEXPIRETIME"),
+            (ProtobufRequestType::HPExpireTime, "HPEXPIRETIME"),
+        ];
+        for (protobuf_request_type, command) in request_types {
+            let request_type = RequestType::from(EnumOrUnknown::new(protobuf_request_type));
+            let cmd = request_type.get_command().unwrap();
+            assert_eq!(cmd.arg_idx(0), Some(command.as_bytes()));
         }
     }
 }
@@ -365,4 +365,60 @@ mod tests {
             args_pointer,
         );
     }
+
+    mod proptests {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            // Malformed input from a binding must fail the parsing, instead of panicking the listener.
+            #[test]
+            fn get_requests_doesnt_panic(chunks in prop::collection::vec(any::<Vec<u8>>(), 1..8)) {
+                let mut rotating_buffer = RotatingBuffer::new(1024);
+                for chunk in chunks {
+                    rotating_buffer.current_buffer().extend_from_slice(&chunk);
+                    if rotating_buffer.get_requests::<RedisRequest>().is_err() {
+                        break;
+                    }
+                }
+            }
+
+            #[test]
+            fn get_requests_regardless_of_split_points(
+                keys in prop::collection::vec("[a-z]{0,64}", 1..16),
+                split_points in prop::collection::vec(any::<prop::sample::Index>(), 0..8),
+            ) {
+                let mut encoded = BytesMut::new();
+                for (index, key) in keys.iter().enumerate() {
+                    write_get(&mut encoded, index as u32, key, false);
+                }
+                let mut split_points: Vec<usize> =
+                    split_points.iter().map(|index| index.index(encoded.len())).collect();
+                split_points.sort();
+                split_points.push(encoded.len());
+
+                let mut rotating_buffer = RotatingBuffer::new(1024);
+                let mut requests = vec![];
+                let mut start = 0;
+                for end in split_points {
+                    rotating_buffer
+                        .current_buffer()
+                        .extend_from_slice(&encoded[start..end]);
+                    requests.extend(rotating_buffer.get_requests::<RedisRequest>().unwrap());
+                    start = end;
+                }
+
+                prop_assert_eq!(requests.len(), keys.len());
+                for (index, (request, key)) in requests.iter().zip(&keys).enumerate() {
+                    assert_request(
+                        request,
+                        RequestType::Get,
+                        index as u32,
+                        vec![Bytes::from(key.clone())],
+                        false,
+                    );
+                }
+            }
+        }
+    }
 }
@@ -2,11 +2,17 @@
  * Copyright Valkey GLIDE Project Contributors - SPDX Identifier: Apache-2.0
  */
 use super::rotating_buffer::RotatingBuffer;
-use crate::client::Client;
+use crate::client::{
+    BroadcastFailurePolicy, Client, ClientSettingsUpdate, ReadFrom, ScheduledTime,
+    DEFAULT_QUEUE_VISIBILITY_TIMEOUT,
+};
+use crate::command_line::parse_command_line;
 use crate::connection_request::ConnectionRequest;
 use crate::errors::{error_message, error_type, RequestErrorType};
 use crate::redis_request::{
-    command, redis_request, Command, RedisRequest, Routes, ScriptInvocation, SlotTypes, Transaction,
+    command, leader_election, queue_operation, redis_request, schedule_command, Broadcast, Command,
+    LeaderElection, LogLevelSetting, QueueOperation, ReadFromSetting, RedisRequest, Routes,
+    ScheduleCommand, ScriptInvocation, SlotTypes, Transaction, UpdateClientSettings,
 };
 use crate::response;
 use crate::response::Response;
@@ -24,6 +30,7 @@ use redis::RedisError;
 use redis::{Cmd, PushInfo, Value};
 use std::cell::Cell;
 use std::rc::Rc;
+use std::time::{Duration, UNIX_EPOCH};
 use std::{env, str};
 use std::{io, thread};
 use thiserror::Error;
@@ -226,6 +233,9 @@ async fn write_result(
                     RequestErrorType::ExecAbort => response::RequestErrorType::ExecAbort,
                     RequestErrorType::Timeout => response::RequestErrorType::Timeout,
                     RequestErrorType::Disconnect => response::RequestErrorType::Disconnect,
+                    RequestErrorType::SlotUnavailable => {
+                        response::RequestErrorType::SlotUnavailable
+                    }
                 }
                 .into(),
                 message: error_message.into(),
@@ -339,6 +349,154 @@ async fn send_transaction(
         .map_err(|err| err.into())
 }
 
+async fn send_broadcast(broadcast: Broadcast, mut client: Client) -> ClientUsageResult<Value> {
+    let Some(command) = broadcast.command.0 else {
+        return Err(ClienUsageError::Internal(
+            "Received broadcast without a command".to_string(),
+        ));
+    };
+    let cmd = get_redis_command(&command)?;
+    let failure_policy = if broadcast.fail_fast {
+        BroadcastFailurePolicy::FailFast
+    } else {
+        BroadcastFailurePolicy::CollectAll
+    };
+    client
+        .broadcast(&cmd, broadcast.primaries_only, failure_policy)
+        .await
+        .map_err(|err| err.into())
+}
+
+fn update_client_settings(
+    settings: UpdateClientSettings,
+    client: &Client,
+) -> ClientUsageResult<Value> {
+    let read_from = settings.read_from.enum_value().map_err(|id| {
+        ClienUsageError::Internal(format!("Received unexpected read from setting {id}"))
+    })?;
+    let log_level = settings.log_level.enum_value().map_err(|id| {
+        ClienUsageError::Internal(format!("Received unexpected log level setting {id}"))
+    })?;
+    let update = ClientSettingsUpdate {
+        request_timeout: match settings.request_timeout_in_ms {
+            0 => None,
+            timeout => Some(Duration::from_millis(timeout.into())),
+        },
+        read_from: match read_from {
+            ReadFromSetting::KeepReadFrom => None,
+            ReadFromSetting::ReadFromPrimary => Some(ReadFrom::Primary),
+            ReadFromSetting::ReadFromPreferReplica => Some(ReadFrom::PreferReplica),
+        },
+        log_level: match log_level {
+            LogLevelSetting::KeepLogLevel => None,
+            LogLevelSetting::LogLevelError => Some(logger_core::Level::Error),
+            LogLevelSetting::LogLevelWarn => Some(logger_core::Level::Warn),
+            LogLevelSetting::LogLevelInfo => Some(logger_core::Level::Info),
+            LogLevelSetting::LogLevelDebug => Some(logger_core::Level::Debug),
+            LogLevelSetting::LogLevelTrace => Some(logger_core::Level::Trace),
+        },
+    };
+    client.update_settings(update).map_err(|err| err.into())
+}
+
+fn schedule_command(
+    schedule: ScheduleCommand,
+    route: Option<Box<Routes>>,
+    client: &Client,
+) -> ClientUsageResult<Value> {
+    let Some(command) = schedule.command.0 else {
+        return Err(ClienUsageError::Internal(
+            "Received scheduled command without a command".to_string(),
+        ));
+    };
+    let cmd = get_redis_command(&command)?;
+    let routing = get_route(route, Some(&cmd))?;
+    let time = match schedule.time {
+        Some(schedule_command::Time::DelayInMs(delay)) => {
+            ScheduledTime::After(Duration::from_millis(delay))
+        }
+        Some(schedule_command::Time::UnixTimeInMs(time)) => {
+            ScheduledTime::At(UNIX_EPOCH + Duration::from_millis(time))
+        }
+        None => {
+            return Err(ClienUsageError::Internal(
+                "Received scheduled command without a time".to_string(),
+            ))
+        }
+    };
+    client
+        .schedule_command(cmd, routing, time)
+        .map_err(|err| err.into())
+}
+
+async fn send_queue_operation(
+    operation: QueueOperation,
+    mut client: Client,
+) -> ClientUsageResult<Value> {
+    let queue = operation.queue.as_ref();
+    let result = match operation.operation {
+        Some(queue_operation::Operation::Enqueue(enqueue)) => {
+            client.queue_enqueue(queue, &enqueue.payload).await
+        }
+        Some(queue_operation::Operation::Reserve(reserve)) => {
+            let visibility_timeout = match reserve.visibility_timeout_in_ms {
+                0 => DEFAULT_QUEUE_VISIBILITY_TIMEOUT,
+                timeout => Duration::from_millis(timeout.into()),
+            };
+            client
+                .queue_reserve(queue, &reserve.consumer, visibility_timeout)
+                .await
+        }
+        Some(queue_operation::Operation::Ack(ack)) => client.queue_ack(queue, &ack.id).await,
+        Some(queue_operation::Operation::Nack(nack)) => {
+            client.queue_nack(queue, &nack.id, nack.dead_letter).await
+        }
+        None => {
+            return Err(ClienUsageError::Internal(
+                "Received empty queue operation".to_string(),
+            ))
+        }
+    };
+    result.map_err(|err| err.into())
+}
+
+async fn send_leader_election(
+    election: LeaderElection,
+    mut client: Client,
+) -> ClientUsageResult<Value> {
+    let key = &election.key;
+    let result = match election.operation {
+        Some(leader_election::Operation::Campaign(campaign)) => {
+            client
+                .leader_campaign(
+                    key,
+                    &campaign.candidate,
+                    Duration::from_millis(campaign.ttl_in_ms.into()),
+                )
+                .await
+        }
+        Some(leader_election::Operation::Renew(renew)) => {
+            client
+                .leader_renew(
+                    key,
+                    &renew.candidate,
+                    Duration::from_millis(renew.ttl_in_ms.into()),
+                )
+                .await
+        }
+        Some(leader_election::Operation::Resign(resign)) => {
+            client.leader_resign(key, &resign.candidate).await
+        }
+        Some(leader_election::Operation::GetLeader(_)) => client.leader(key).await,
+        None => {
+            return Err(ClienUsageError::Internal(
+                "Received empty leader election operation".to_string(),
+            ))
+        }
+    };
+    result.map_err(|err| err.into())
+}
+
 fn get_slot_addr(slot_type: &protobuf::EnumOrUnknown<SlotTypes>) -> ClientUsageResult<SlotAddr> {
     slot_type
         .enum_value()
@@ -411,13 +569,17 @@ fn get_route(
     }
 }
 
-fn handle_request(request: RedisRequest, client: Client, writer: Rc<Writer>) {
+fn handle_request(request: RedisRequest, mut client: Client, writer: Rc<Writer>) {
     task::spawn_local(async move {
         let result = match request.command {
             Some(action) => match action {
                 redis_request::Command::SingleCommand(command) => {
                     match get_redis_command(&command) {
                         Ok(cmd) => match get_route(request.route.0, Some(&cmd)) {
+                            Ok(routes) if request.pin_node => client
+                                .send_command_pinned(&cmd, routes)
+                                .await
+                                .map_err(|err| err.into()),
                             Ok(routes) => send_command(cmd, client, routes).await,
                             Err(e) => Err(e),
                         },
@@ -436,6 +598,55 @@ fn handle_request(request: RedisRequest, client: Client, writer: Rc<Writer>) {
                         Err(e) => Err(e),
                     }
                 }
+                redis_request::Command::CommandLine(line) => match parse_command_line(&line) {
+                    Ok(cmd) => match get_route(request.route.0, Some(&cmd)) {
+                        Ok(routes) => send_command(cmd, client, routes).await,
+                        Err(e) => Err(e),
+                    },
+                    Err(err) => Err(ClienUsageError::User(err.to_string())),
+                },
+                redis_request::Command::Statistics(_) => Ok(client.statistics()),
+                redis_request::Command::ServerCapabilities(capabilities) => client
+                    .server_capabilities_report(capabilities.refresh)
+                    .await
+                    .map_err(|err| err.into()),
+                redis_request::Command::UpdateClientSettings(settings) => {
+                    update_client_settings(settings, &client)
+                }
+                redis_request::Command::ScheduleCommand(schedule) => {
+                    schedule_command(schedule, request.route.0, &client)
+                }
+                redis_request::Command::CancelScheduledCommand(cancel) => {
+                    Ok(client.cancel_scheduled_command(cancel.id))
+                }
+                redis_request::Command::Broadcast(broadcast) => {
+                    send_broadcast(broadcast, client).await
+                }
+                redis_request::Command::QueueOperation(operation) => {
+                    send_queue_operation(operation, client).await
+                }
+                redis_request::Command::LeaderElection(election) => {
+                    send_leader_election(election, client).await
+                }
+                redis_request::Command::IdempotencyCheckAndSet(check) => client
+                    .idempotency_check_and_set(
+                        &check.key,
+                        Duration::from_millis(check.ttl_in_ms.into()),
+                    )
+                    .await
+                    .map_err(|err| err.into()),
+                redis_request::Command::IdempotencyRelease(release) => client
+                    .idempotency_release(&release.key)
+                    .await
+                    .map_err(|err| err.into()),
+                redis_request::Command::WaitForSubscriptions(wait) => client
+                    .wait_for_subscriptions(match wait.timeout_in_ms {
+                        0 => None,
+                        timeout => Some(Duration::from_millis(timeout.into())),
+                    })
+                    .await
+                    .map(|_| Value::Okay)
+                    .map_err(|err| err.into()),
             },
             None => {
                 log_debug(
@@ -522,6 +733,13 @@ async fn read_values_loop(
     }
 }
 
+/// Forwards push notifications to the wrapper, in the order they were received from the server.
+/// The notifications of all connections pass through a single unbounded channel, and each one is written before the
+/// next is read, so messages of the same channel are never reordered or dropped on their way to the wrapper.
+/// Messages published while a connection is down are lost - the `Disconnection` notification sent by the
+/// connection, and the `Reconnection` notification sent when a standalone connection starts reconnecting, mark the
+/// point at which such a gap may start. A notification that can't be written would be an unmarked gap, so the loop
+/// stops on the first failed write, which closes the client.
 async fn push_manager_loop(mut push_rx: mpsc::UnboundedReceiver<PushInfo>, writer: Rc<Writer>) {
     loop {
         let result = push_rx.recv().await;
@@ -545,7 +763,13 @@ async fn push_manager_loop(mut push_rx: mpsc::UnboundedReceiver<PushInfo>, write
                     Some(response::response::Value::RespPointer(raw_pointer as u64))
                 };
 
-                _ = write_to_writer(response, &writer).await;
+                if let Err(err) = write_to_writer(response, &writer).await {
+                    log_error(
+                        "push manager loop",
+                        format!("failed to write push notification, closing: {err}"),
+                    );
+                    return;
+                }
             }
         }
     }
@@ -625,6 +849,7 @@ async fn listen_on_client_stream(socket: UnixStream) {
                 log_trace("client closing", "push manager closed");
             }
     }
+    client.cancel_all_scheduled_commands();
     log_trace("client closing", "closing connection");
 }
 
@@ -1237,6 +1237,30 @@ mod socket_listener {
         );
     }
 
+    #[rstest]
+    #[serial_test::serial]
+    #[timeout(SHORT_CLUSTER_TEST_TIMEOUT)]
+    fn test_statistics_are_empty_when_not_configured() {
+        // The standalone client always reports its nodes' statistics.
+        let mut test_basics = setup_test_basics(Tls::NoTls, TestServer::Shared, RedisType::Cluster);
+        const CALLBACK_INDEX: u32 = 7;
+
+        let mut buffer = Vec::with_capacity(APPROX_RESP_HEADER_LEN);
+        let mut request = RedisRequest::new();
+        request.callback_idx = CALLBACK_INDEX;
+        request.command = Some(redis_request::redis_request::Command::Statistics(
+            redis_request::Statistics::new(),
+        ));
+        write_request(&mut buffer, &mut test_basics.socket, request);
+
+        assert_value_response(
+            &mut buffer,
+            Some(&mut test_basics.socket),
+            CALLBACK_INDEX,
+            Value::Map(vec![]),
+        );
+    }
+
     #[rstest]
     #[serial_test::serial]
     #[timeout(SHORT_CLUSTER_TEST_TIMEOUT)]
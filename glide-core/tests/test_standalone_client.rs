@@ -11,10 +11,10 @@ mod standalone_client_tests {
 
     use super::*;
     use glide_core::{
-        client::{ConnectionError, StandaloneClient},
+        client::{ConnectionError, StandaloneClient, RECONNECTION_PUSH_KIND},
         connection_request::ReadFrom,
     };
-    use redis::{FromRedisValue, Value};
+    use redis::{FromRedisValue, PushKind, Value};
     use rstest::rstest;
     use utilities::*;
 
@@ -92,6 +92,36 @@ mod standalone_client_tests {
         });
     }
 
+    #[rstest]
+    #[serial_test::serial]
+    #[timeout(SHORT_STANDALONE_TEST_TIMEOUT)]
+    fn test_reconnect_sends_reconnection_push_notification() {
+        block_on_all(async move {
+            let mut test_basics = setup_test_basics_internal(&TestConfiguration {
+                shared_server: true,
+                ..Default::default()
+            })
+            .await;
+            let mut client = test_basics.client;
+
+            kill_connection(&mut client).await;
+            let mut get_command = redis::Cmd::new();
+            get_command
+                .arg("GET")
+                .arg("test_reconnect_sends_reconnection_push_notification");
+            // The failed request starts the reconnect.
+            let _ = client.send_command(&get_command).await;
+
+            loop {
+                let push_info = test_basics.push_receiver.recv().await.unwrap();
+                if matches!(&push_info.kind, PushKind::Other(kind) if kind == RECONNECTION_PUSH_KIND)
+                {
+                    break;
+                }
+            }
+        });
+    }
+
     fn get_mock_addresses(mocks: &[ServerMock]) -> Vec<redis::ConnectionAddr> {
         mocks.iter().flat_map(|mock| mock.get_addresses()).collect()
     }
@@ -59,7 +59,14 @@ fn redis_value_to_java<'local>(
         Value::Double(float) => Ok(env.new_object("java/lang/Double", "(D)V", &[float.into()])?),
         Value::Boolean(bool) => Ok(env.new_object("java/lang/Boolean", "(Z)V", &[bool.into()])?),
         Value::VerbatimString { format: _, text } => Ok(JObject::from(env.new_string(text)?)),
-        Value::BigNumber(_num) => todo!(),
+        Value::BigNumber(num) => {
+            let num_str = env.new_string(num.to_string())?;
+            Ok(env.new_object(
+                "java/math/BigInteger",
+                "(Ljava/lang/String;)V",
+                &[(&num_str).into()],
+            )?)
+        }
         Value::Set(array) => {
             let set = env.new_object("java/util/HashSet", "()V", &[])?;
 
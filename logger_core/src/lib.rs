@@ -147,6 +147,33 @@ pub fn init(minimal_level: Option<Level>, file_name: Option<&str>) -> Level {
     level
 }
 
+// Changes the level of the initialized logger, without changing whether it writes to the console or to a file.
+// Has no effect if the logger wasn't initialized.
+pub fn set_level(level: Level) {
+    let Some(reloads) = INITIATE_ONCE.init_once.get() else {
+        return;
+    };
+    let level_filter = level.to_filter();
+    let _ = reloads
+        .console_reload
+        .write()
+        .expect("error reloading stdout")
+        .modify(|layer| {
+            if *layer.filter() != LevelFilter::OFF {
+                *layer.filter_mut() = level_filter;
+            }
+        });
+    let _ = reloads
+        .file_reload
+        .write()
+        .expect("error reloading file appender")
+        .modify(|layer| {
+            if *layer.filter() != LevelFilter::OFF {
+                *layer.filter_mut() = level_filter;
+            }
+        });
+}
+
 macro_rules! create_log {
     ($name:ident, $uppercase_level:tt) => {
         pub fn $name<Message: AsRef<str>, Identifier: AsRef<str>>(
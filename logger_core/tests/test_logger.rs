@@ -7,7 +7,7 @@ use test_env_helpers::*;
 #[after_all]
 #[before_all]
 mod tests {
-    use logger_core::{init, log_debug, log_trace};
+    use logger_core::{init, log_debug, log_trace, set_level};
     use rand::{distributions::Alphanumeric, Rng};
     use std::fs::{read_dir, read_to_string, remove_dir_all};
     const FILE_DIRECTORY: &str = "glide-logs";
@@ -83,6 +83,18 @@ mod tests {
         assert!(!contents.contains("boo"));
     }
 
+    #[test]
+    fn log_to_file_follows_set_level() {
+        let identifier = generate_random_string(10);
+        init(Some(logger_core::Level::Trace), Some(identifier.as_str()));
+        set_level(logger_core::Level::Debug);
+        log_debug(identifier.clone(), "foo");
+        log_trace(identifier.clone(), "boo");
+        let contents = get_file_contents(identifier.as_str());
+        assert!(contents.contains("foo"), "Contents: {}", contents);
+        assert!(!contents.contains("boo"));
+    }
+
     #[test]
     fn log_to_file_disabled_when_console_init() {
         let identifier = generate_random_string(10);